@@ -0,0 +1,48 @@
+//! Counts the allocations made when adding context on the error path.
+//!
+//! `ResultExt::context_static` borrows its message, so it needs one allocation
+//! less per error than `ResultExt::context`. Run with `cargo bench`.
+use std::{
+	alloc::{GlobalAlloc, Layout, System},
+	sync::atomic::{AtomicUsize, Ordering},
+};
+
+use easy_error::{Error, ResultExt};
+
+const ITERATIONS: usize = 100_000;
+
+/// An allocator that counts the allocations it makes.
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting
+{
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8
+	{
+		ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+		System.alloc(layout)
+	}
+
+	unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) { System.dealloc(ptr, layout) }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+fn measure<F: FnMut() -> Result<u32, Error>>(name: &str, mut f: F)
+{
+	let before = ALLOCATIONS.load(Ordering::Relaxed);
+	for _ in 0..ITERATIONS {
+		drop(f());
+	}
+	let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+
+	println!("{:<16} {:>6.2} allocations/iter", name, allocations as f64 / ITERATIONS as f64);
+}
+
+fn main()
+{
+	measure("context", || "abc".parse::<u32>().context("Invalid number"));
+	measure("context_static", || "abc".parse::<u32>().context_static("Invalid number"));
+}
@@ -0,0 +1,38 @@
+//! Measures the cost of adding context on the success path.
+//!
+//! This compares `ResultExt::context` with a plain `Result`. Every way of
+//! creating an `Error` is marked `#[cold]`, so there is no version without it
+//! to compare with here. Run with `cargo bench`.
+#![allow(clippy::incompatible_msrv)] // Benchmarks are not held to the minimum Rustc version
+use std::{
+	hint::black_box,
+	time::{Duration, Instant},
+};
+
+use easy_error::{Error, ResultExt};
+
+const ITERATIONS: u32 = 10_000_000;
+
+fn parse(input: &str) -> Result<u32, Error> { input.parse::<u32>().context("Invalid number") }
+
+fn parse_plain(input: &str) -> Result<u32, std::num::ParseIntError> { input.parse::<u32>() }
+
+fn measure<F: FnMut() -> u32>(name: &str, mut f: F)
+{
+	let start = Instant::now();
+	let mut total = 0_u32;
+	for _ in 0..ITERATIONS {
+		total = total.wrapping_add(black_box(f()));
+	}
+	let elapsed = start.elapsed();
+
+	println!("{:<12} {:>8.2} ns/iter (checksum {})", name, per_iteration(elapsed), total);
+}
+
+fn per_iteration(elapsed: Duration) -> f64 { elapsed.as_secs_f64() * 1e9 / f64::from(ITERATIONS) }
+
+fn main()
+{
+	measure("plain", || parse_plain(black_box("12345")).unwrap_or(0));
+	measure("context", || parse(black_box("12345")).unwrap_or(0));
+}
@@ -0,0 +1,59 @@
+//! Incremental construction of errors.
+use std::{
+	fmt::{self, Write},
+	panic::Location,
+};
+
+use crate::{Error, location};
+
+/// Builds the message of an `Error` piece by piece.
+///
+/// The builder implements `fmt::Write`, so the message can be assembled with
+/// repeated calls to `write!`. The location of the error is the place where
+/// the builder was created.
+///
+/// ```
+/// use std::fmt::Write;
+/// use easy_error::ErrorBuilder;
+///
+/// let mut builder = ErrorBuilder::new();
+/// write!(builder, "Unexpected token '{}'", '}').unwrap();
+/// write!(builder, " on line {}", 12).unwrap();
+///
+/// let err = builder.build();
+/// assert_eq!(err.ctx(), "Unexpected token '}' on line 12");
+/// ```
+#[derive(Debug)]
+pub struct ErrorBuilder
+{
+	/// The message written so far.
+	ctx: String,
+
+	/// The location where the builder was created.
+	location: &'static Location<'static>,
+}
+
+impl ErrorBuilder
+{
+	/// Creates a new builder with an empty message.
+	#[must_use]
+	#[cfg_attr(not(feature = "no-location"), track_caller)]
+	pub fn new() -> ErrorBuilder
+	{
+		ErrorBuilder { ctx: String::new(), location: location::caller() }
+	}
+
+	/// Finishes the message and creates the error.
+	pub fn build(self) -> Error { Error::from_parts(self.ctx, self.location, None) }
+}
+
+impl Default for ErrorBuilder
+{
+	#[cfg_attr(not(feature = "no-location"), track_caller)]
+	fn default() -> ErrorBuilder { ErrorBuilder::new() }
+}
+
+impl Write for ErrorBuilder
+{
+	fn write_str(&mut self, s: &str) -> fmt::Result { self.ctx.write_str(s) }
+}
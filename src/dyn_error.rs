@@ -0,0 +1,72 @@
+//! Type-erased errors for dynamic boundaries.
+use std::{
+	error,
+	fmt::{self, Display, Formatter},
+};
+
+/// A boxed error of any type that implements `std::error::Error` itself.
+///
+/// A `Box<dyn Error + Send>` does not implement `std::error::Error`, so it
+/// cannot be given context with `ResultExt` or used as the cause of an `Error`.
+/// This wrapper fills that gap for interfaces, such as plugin traits, that
+/// return boxed errors. It is transparent: its message and source are those of
+/// the wrapped error.
+///
+/// ```
+/// use easy_error::{DynError, ErrorExt, ResultExt};
+///
+/// trait Plugin {
+///     fn run(&self) -> Result<(), DynError>;
+/// }
+///
+/// struct Parser;
+///
+/// impl Plugin for Parser {
+///     fn run(&self) -> Result<(), DynError> {
+///         "abc".parse::<i32>().map_err(DynError::new)?;
+///         Ok(())
+///     }
+/// }
+///
+/// let plugin: Box<dyn Plugin> = Box::new(Parser);
+/// let err = plugin.run().context("Plugin failed").unwrap_err();
+/// assert_eq!(err.find_root_cause().to_string(), "invalid digit found in string");
+/// ```
+#[derive(Debug)]
+pub struct DynError
+{
+	/// The wrapped error.
+	inner: Box<dyn error::Error + Send + 'static>,
+}
+
+impl DynError
+{
+	/// Boxes the error.
+	pub fn new<E: error::Error + Send + 'static>(err: E) -> DynError
+	{
+		DynError { inner: Box::new(err) }
+	}
+
+	/// Returns a reference to the wrapped error.
+	#[must_use]
+	pub fn get_ref(&self) -> &(dyn error::Error + Send + 'static) { &*self.inner }
+
+	/// Returns the wrapped error.
+	#[must_use]
+	pub fn into_inner(self) -> Box<dyn error::Error + Send + 'static> { self.inner }
+}
+
+impl Display for DynError
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result { Display::fmt(&self.inner, f) }
+}
+
+impl error::Error for DynError
+{
+	fn source(&self) -> Option<&(dyn error::Error + 'static)> { self.inner.source() }
+}
+
+impl From<Box<dyn error::Error + Send + 'static>> for DynError
+{
+	fn from(inner: Box<dyn error::Error + Send + 'static>) -> DynError { DynError { inner } }
+}
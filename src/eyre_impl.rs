@@ -0,0 +1,116 @@
+//! Conversions between errors and `eyre` reports.
+use std::{
+	error,
+	fmt::{self, Display, Formatter},
+};
+
+use eyre::Report;
+
+use crate::{Error, iter_chain, location};
+
+/// Converts a report into an error.
+///
+/// The message of the report becomes the message of the error, and the rest
+/// of its chain becomes the causes of the error.
+///
+/// ```
+/// use easy_error::{Error, ErrorExt};
+///
+/// let report = eyre::eyre!("Timed out").wrap_err("Upload failed");
+/// let err = Error::from(report);
+///
+/// assert_eq!(err.ctx(), "Upload failed");
+/// assert_eq!(err.find_root_cause().to_string(), "Timed out");
+/// ```
+impl From<Report> for Error
+{
+	#[cfg_attr(not(feature = "no-location"), track_caller)]
+	fn from(report: Report) -> Error
+	{
+		let ctx = report.to_string();
+		let cause: Option<Box<dyn error::Error + Send + 'static>> =
+			if report.source().is_some() { Some(Box::new(Tail(report))) } else { None };
+
+		Error::from_parts(ctx, location::caller(), cause)
+	}
+}
+
+impl Error
+{
+	/// Converts the error into an `eyre` report.
+	///
+	/// A `From` implementation would overlap with the one `eyre` provides for
+	/// every error that is `Send` and `Sync`. The `Error` type is not `Sync`,
+	/// which a report requires, so the chain is copied as text. Each link keeps
+	/// its message and, for `Error`s from this crate, its location, but the
+	/// original types can no longer be downcast to.
+	///
+	/// ```
+	/// use easy_error::{err_msg, Error};
+	///
+	/// let report = Error::new("Upload failed", err_msg("Timed out")).into_report();
+	/// let chain: Vec<_> = report.chain().map(|e| e.to_string()).collect();
+	///
+	/// assert_eq!(chain.len(), 2);
+	/// assert!(chain[1].starts_with("Timed out"));
+	/// ```
+	pub fn into_report(self) -> Report
+	{
+		let messages: Vec<_> = iter_chain(&self).map(ToString::to_string).collect();
+		let mut messages = messages.into_iter().rev();
+
+		let root = Detached { message: messages.next().unwrap_or_default(), source: None };
+		let detached = messages
+			.fold(root, |source, message| Detached { message, source: Some(Box::new(source)) });
+
+		Report::new(detached)
+	}
+}
+
+/// The causes of a report, without its own message.
+///
+/// The report keeps ownership of the chain, so this shows the first cause of
+/// the report as its own message.
+struct Tail(Report);
+
+impl fmt::Debug for Tail
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result { fmt::Debug::fmt(&self.0, f) }
+}
+
+impl Display for Tail
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		self.0.source().map_or(Ok(()), |source| Display::fmt(source, f))
+	}
+}
+
+impl error::Error for Tail
+{
+	fn source(&self) -> Option<&(dyn error::Error + 'static)>
+	{
+		self.0.source().and_then(error::Error::source)
+	}
+}
+
+/// A link of a chain that was copied as text.
+#[derive(Debug)]
+struct Detached
+{
+	message: String,
+	source:  Option<Box<Detached>>,
+}
+
+impl Display for Detached
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result { f.write_str(&self.message) }
+}
+
+impl error::Error for Detached
+{
+	fn source(&self) -> Option<&(dyn error::Error + 'static)>
+	{
+		self.source.as_ref().map(|s| &**s as _)
+	}
+}
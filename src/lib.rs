@@ -20,6 +20,40 @@
 //! Rustc than what is available on the oldest supported Ubuntu LTS will
 //! be considered a breaking change.
 //!
+//! ## Optional Features
+//!
+//! Optional features are allowed to require a newer version of Rustc than the
+//! rest of the crate.
+//!
+//! * `color` - Colors the `Terminator` output when printing to a terminal.
+//!   Requires Rustc 1.70 or newer.
+//! * `capture-env` - Adds `Error::capture_env` for recording environment
+//!   variables in the fields of an error.
+//! * `eyre` - Converts between `Error` and `eyre::Report`.
+//! * `log` - Adds `ResultExt::unwrap_or_default_logged`, which logs errors
+//!   with the `log` crate.
+//! * `miette` - Implements `miette::Diagnostic` for `Error`.
+//! * `no-location` - Stops recording the locations of errors, which are then
+//!   left out of the output, to make binaries smaller. In the stripped release
+//!   build of the `basic` example, which adds context in three places, this
+//!   saves about 1.5 KB.
+//! * `regex` - Adds `ErrorExt::chain_matches` and regular expression patterns
+//!   for `matches_err!`.
+//! * `sections` - Adds `TerminatorSection` and `register_section` for
+//!   appending custom sections to the `Terminator` output. Requires Rustc 1.70
+//!   or newer.
+//! * `sequence-id` - Gives every `Error` a unique sequence number, which is
+//!   shown in the `Terminator` output.
+//! * `serde` - Implements `Serialize` and `Deserialize` for `Error`.
+//! * `termination` - Implements `Termination` for `Error` and `Terminator`,
+//!   and adds `ExitCoded` for choosing the exit code when `main` returns
+//!   `ExitCode`. Requires Rustc 1.61 or newer.
+//! * `thread-info` - Records the thread an `Error` was created on, which is
+//!   returned by `Error::thread` and `Error::thread_id`, and shows its name in
+//!   the `Terminator` output.
+//! * `tracing` - Adds `Error::trace_event` for recording an error as a
+//!   `tracing` event with separate fields for its parts.
+//!
 //! ## Example
 //!
 //! ```no_run
@@ -64,55 +98,775 @@
 #![warn(unknown_lints)]
 
 use std::{
+	borrow::Cow,
+	collections::BTreeMap,
+	convert::Infallible,
 	error,
 	fmt::{self, Display, Formatter},
+	io,
+	iter::FusedIterator,
 	panic::Location,
+	str::FromStr,
 	string::ToString,
+	time::Instant,
 };
 
+#[cfg(feature = "capture-env")]
+use std::env;
+#[cfg(feature = "sequence-id")]
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(feature = "thread-info")]
+use std::thread::{self, ThreadId};
+
+use location::Origin;
+
+mod builder;
+mod dyn_error;
+#[cfg(feature = "eyre")]
+mod eyre_impl;
+mod location;
 mod macros;
+#[cfg(feature = "miette")]
+mod miette_impl;
+mod multi;
+mod scope;
+#[cfg(feature = "sections")]
+mod section;
+#[cfg(feature = "serde")]
+mod serde_impl;
 mod terminator;
-pub use terminator::Terminator;
+#[cfg(feature = "tracing")]
+mod tracing_impl;
+pub use builder::ErrorBuilder;
+pub use dyn_error::DynError;
+pub use location::{LocationStyle, location_style, set_location_style};
+pub use multi::MultiError;
+#[cfg(feature = "regex")]
+#[doc(hidden)]
+pub use regex::Regex as __Regex;
+pub use scope::ContextScope;
+#[cfg(feature = "sections")]
+pub use section::{EnvSection, TerminatorSection, register_section};
+#[cfg(feature = "termination")]
+pub use terminator::ExitCoded;
+pub use terminator::{
+	SendTerminator,
+	Stream,
+	Terminator,
+	TerminatorResultExt,
+	install_panic_hook,
+	print_error,
+};
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// The ID given to the next `Error` that is created.
+#[cfg(feature = "sequence-id")]
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
 /// An error that is a human-targetted string plus an optional cause.
+// An error that is created and then dropped is almost certainly a missing `return` or `?`.
+#[must_use = "errors do nothing unless they are returned or handled"]
 #[derive(Debug)]
 pub struct Error
 {
 	/// The human-targetting error string.
-	pub ctx: String,
+	ctx: Cow<'static, str>,
 
 	/// The location of the error.
-	pub location: &'static Location<'static>,
+	location: &'static Location<'static>,
 
 	/// The optional cause of the error.
-	pub cause: Option<Box<dyn error::Error + Send + 'static>>,
+	cause: Option<Box<dyn error::Error + Send + 'static>>,
+
+	/// The parts of the error that are rarely set, if any of them are.
+	extra: Option<Box<Extra>>,
+
+	/// The name of the thread the error was created on, if it has one.
+	#[cfg(feature = "thread-info")]
+	thread: Option<String>,
+
+	/// The ID of the thread the error was created on.
+	#[cfg(feature = "thread-info")]
+	thread_id: ThreadId,
+
+	/// The sequence number of the error.
+	#[cfg(feature = "sequence-id")]
+	id: u64,
 }
 
 impl Error
 {
 	/// Create a new error with the given cause.
+	///
+	/// The context can be anything that implements `ToString`, including the
+	/// `fmt::Arguments` created by `format_args!`.
+	///
+	/// ```
+	/// use easy_error::{err_msg, Error};
+	///
+	/// let path = "app.toml";
+	/// let err = Error::new(format_args!("Could not read {}", path), err_msg("Not found"));
+	/// assert_eq!(err.ctx(), "Could not read app.toml");
+	/// ```
 	#[allow(clippy::needless_pass_by_value)] // `T: ToString` implies `&T: ToString`
-	#[track_caller]
+	#[cfg_attr(not(feature = "no-location"), track_caller)]
 	pub fn new<S, E>(ctx: S, cause: E) -> Error
 	where
 		S: ToString,
 		E: error::Error + Send + 'static,
 	{
 		let ctx = ctx.to_string();
-		let location = Location::caller();
+		let location = location::caller();
 		let cause: Option<Box<dyn error::Error + Send + 'static>> = Some(Box::new(cause));
 
-		Error { ctx, location, cause }
+		Error::from_parts(ctx, location, cause)
+	}
+
+	/// Wraps the cause with the given message.
+	///
+	/// This is the same as `Error::new` with the arguments swapped, for code
+	/// that reads better with the cause first.
+	///
+	/// ```
+	/// use easy_error::{err_msg, Error};
+	///
+	/// let err = Error::wrap(err_msg("Disk full"), "Could not save file");
+	/// assert_eq!(err.ctx(), "Could not save file");
+	/// ```
+	#[allow(clippy::needless_pass_by_value)] // `T: ToString` implies `&T: ToString`
+	#[cfg_attr(not(feature = "no-location"), track_caller)]
+	pub fn wrap<E, S>(cause: E, ctx: S) -> Error
+	where
+		E: error::Error + Send + 'static,
+		S: ToString,
+	{
+		Error::from_parts(ctx.to_string(), location::caller(), Some(Box::new(cause)))
+	}
+
+	/// Create a new error with an already boxed cause.
+	///
+	/// This accepts the `Box<dyn Error + Send + Sync>` used by many libraries,
+	/// which does not itself implement `std::error::Error` and so cannot be
+	/// passed to `Error::new`.
+	///
+	/// ```
+	/// use std::error::Error as StdError;
+	/// use easy_error::Error;
+	///
+	/// let cause: Box<dyn StdError + Send + Sync> = "Connection reset".into();
+	/// let err = Error::new_boxed("Task failed", cause);
+	///
+	/// assert_eq!(err.cause().unwrap().to_string(), "Connection reset");
+	/// ```
+	#[allow(clippy::needless_pass_by_value)] // `T: ToString` implies `&T: ToString`
+	#[cfg_attr(not(feature = "no-location"), track_caller)]
+	pub fn new_boxed<S: ToString>(ctx: S, cause: Box<dyn error::Error + Send + 'static>) -> Error
+	{
+		Error::from_parts(ctx.to_string(), location::caller(), Some(cause))
+	}
+
+	/// Creates an error from its parts, filling in any captured information.
+	///
+	/// Every error is created through here. It is marked cold so that the code
+	/// for creating errors is kept away from the code of the success path.
+	#[cold]
+	#[inline(never)]
+	pub(crate) fn from_parts(
+		ctx: impl Into<Cow<'static, str>>,
+		location: &'static Location<'static>,
+		cause: Option<Box<dyn error::Error + Send + 'static>>,
+	) -> Error
+	{
+		#[cfg(feature = "thread-info")]
+		let current = thread::current();
+
+		let context_stack = scope::snapshot();
+		let extra = if context_stack.is_empty() {
+			None
+		}
+		else {
+			Some(Box::new(Extra { context_stack, ..Extra::default() }))
+		};
+
+		Error {
+			ctx: ctx.into(),
+			location,
+			cause,
+			extra,
+			#[cfg(feature = "thread-info")]
+			thread: current.name().map(String::from),
+			#[cfg(feature = "thread-info")]
+			thread_id: current.id(),
+			#[cfg(feature = "sequence-id")]
+			id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+		}
+	}
+
+	/// Returns the sequence number of the error.
+	///
+	/// Every error is given a unique number when it is created, counting up
+	/// from `1` across all threads, so errors can be matched between log lines
+	/// and the `Terminator` output.
+	///
+	/// ```
+	/// use easy_error::err_msg;
+	///
+	/// let first = err_msg("First");
+	/// let second = err_msg("Second");
+	/// assert!(second.id() > first.id());
+	/// ```
+	#[cfg(feature = "sequence-id")]
+	#[must_use]
+	pub const fn id(&self) -> u64 { self.id }
+
+	/// Returns the name of the thread the error was created on, if it has one.
+	///
+	/// ```
+	/// let err = std::thread::Builder::new()
+	///     .name(String::from("worker"))
+	///     .spawn(|| easy_error::err_msg("Timed out"))
+	///     .unwrap()
+	///     .join()
+	///     .unwrap();
+	///
+	/// assert_eq!(err.thread(), Some("worker"));
+	/// assert_ne!(err.thread_id(), std::thread::current().id());
+	/// ```
+	#[cfg(feature = "thread-info")]
+	#[must_use]
+	pub fn thread(&self) -> Option<&str> { self.thread.as_deref() }
+
+	/// Returns the ID of the thread the error was created on.
+	#[cfg(feature = "thread-info")]
+	#[must_use]
+	pub const fn thread_id(&self) -> ThreadId { self.thread_id }
+
+	/// Returns the human-targetting error string.
+	#[must_use]
+	pub fn ctx(&self) -> &str { &self.ctx }
+
+	/// Returns the location where the error was created.
+	///
+	/// With the `no-location` feature, every error has the same placeholder
+	/// location.
+	#[must_use]
+	pub const fn location(&self) -> &'static Location<'static> { self.location }
+
+	/// Returns where the error was created, preferring the original location
+	/// of a rebuilt error over the one recorded in this process.
+	///
+	/// This is `None` for a rebuilt error whose original location is unknown.
+	#[cfg_attr(
+		not(feature = "serde"),
+		allow(clippy::missing_const_for_fn, clippy::unnecessary_wraps)
+	)] // Errors are only rebuilt with `serde`
+	fn origin(&self) -> Option<Origin<'_>>
+	{
+		#[cfg(feature = "serde")]
+		if let Some(extra) = self.extra.as_ref().filter(|e| e.rebuilt) {
+			return extra.remote_location.as_deref().map(Origin::Remote);
+		}
+
+		Some(Origin::Local(self.location))
+	}
+
+	/// Returns the cause of the error, if it has one.
+	///
+	/// ```
+	/// use easy_error::{err_msg, Error};
+	///
+	/// let err = Error::new("Request failed", err_msg("Timed out"));
+	/// assert!(err.cause().unwrap().to_string().starts_with("Timed out"));
+	/// ```
+	#[must_use]
+	pub fn cause(&self) -> Option<&(dyn error::Error + Send + 'static)> { self.cause.as_deref() }
+
+	/// Returns a mutable reference to the cause of the error.
+	///
+	/// ```
+	/// use easy_error::{err_msg, Error};
+	///
+	/// let mut err = Error::new("Request failed", err_msg("Timed out"));
+	/// if let Some(inner) = err.cause_mut().and_then(|c| c.downcast_mut::<Error>()) {
+	///     inner.fields_mut().insert(String::from("seconds"), String::from("30"));
+	/// }
+	///
+	/// let inner = err.cause().and_then(|c| c.downcast_ref::<Error>()).unwrap();
+	/// assert_eq!(inner.field("seconds"), Some("30"));
+	/// ```
+	pub fn cause_mut(&mut self) -> Option<&mut (dyn error::Error + Send + 'static)>
+	{
+		self.cause.as_deref_mut()
+	}
+
+	/// Returns the value of a key-value field of the error, if it is set.
+	///
+	/// ```
+	/// let err = easy_error::err_msg("Request failed").with_field("status", 503);
+	///
+	/// assert_eq!(err.field("status"), Some("503"));
+	/// assert_eq!(err.field("host"), None);
+	/// ```
+	#[must_use]
+	pub fn field(&self, key: &str) -> Option<&str>
+	{
+		self.extra.as_ref().and_then(|e| e.fields.get(key)).map(String::as_str)
+	}
+
+	/// Returns the key-value fields of the error, ordered by key.
+	///
+	/// ```
+	/// let err = easy_error::err_msg("Request failed").with_field("status", 503).with_field("host", "db1");
+	/// let fields: Vec<_> = err.fields().collect();
+	///
+	/// assert_eq!(fields, [("host", "db1"), ("status", "503")]);
+	/// ```
+	pub fn fields(&self) -> impl Iterator<Item = (&str, &str)> + '_
+	{
+		self.extra.iter().flat_map(|e| &e.fields).map(|(k, v)| (k.as_str(), v.as_str()))
+	}
+
+	/// Returns a mutable reference to the key-value fields of the error.
+	pub fn fields_mut(&mut self) -> &mut BTreeMap<String, String> { &mut self.extra_mut().fields }
+
+	/// Returns the errors that occurred alongside this one, such as failed
+	/// fallbacks, in the order they were added with `or_also`.
+	pub fn also(&self) -> &[Error] { self.extra.as_ref().map_or(&[], |e| &e.also) }
+
+	/// Returns the link to documentation about the error, if it has one.
+	#[must_use]
+	pub fn url(&self) -> Option<&str> { self.extra.as_ref().and_then(|e| e.url.as_deref()) }
+
+	/// Returns the messages of the `ContextScope`s that were active when the
+	/// error was created, outermost first.
+	#[must_use]
+	pub fn context_stack(&self) -> &[String]
+	{
+		self.extra.as_ref().map_or(&[], |e| &e.context_stack)
+	}
+
+	/// Returns the path of the function the error was created in, if it was
+	/// recorded by `err_here!`.
+	#[must_use]
+	pub fn function(&self) -> Option<&'static str> { self.extra.as_ref().and_then(|e| e.function) }
+
+	/// Records the function the error was created in, for `err_here!`.
+	#[doc(hidden)]
+	pub fn __with_function(mut self, function: &'static str) -> Error
+	{
+		self.extra_mut().function = Some(function);
+		self
+	}
+
+	/// Returns the rarely set parts of the error, creating them if needed.
+	fn extra_mut(&mut self) -> &mut Extra { self.extra.get_or_insert_with(Box::default) }
+
+	/// Places the error at the root of the chain, below the last of the
+	/// `Error`s that lead to it.
+	///
+	/// The error is given back if the chain ends in another type of error.
+	fn attach_root(
+		&mut self,
+		root: Box<dyn error::Error + Send + 'static>,
+	) -> std::result::Result<(), Box<dyn error::Error + Send + 'static>>
+	{
+		match &mut self.cause {
+			None => {
+				self.cause = Some(root);
+				Ok(())
+			},
+			Some(cause) => match cause.downcast_mut::<Error>() {
+				Some(cause) => cause.attach_root(root),
+				None => Err(root),
+			},
+		}
+	}
+
+	/// Returns the names of the types of the links of the chain.
+	///
+	/// The concrete type of a link cannot be named through a `dyn Error`, so
+	/// this only recognizes the error types of this crate and a few common
+	/// ones from the standard library. Every other link is reported as
+	/// `"dyn std::error::Error"`.
+	///
+	/// ```
+	/// use easy_error::ResultExt;
+	///
+	/// let err = "abc".parse::<i32>().context("Invalid number").unwrap_err();
+	/// let names = err.cause_type_names();
+	///
+	/// assert_eq!(names, ["easy_error::Error", "std::num::ParseIntError"]);
+	/// ```
+	#[must_use]
+	pub fn cause_type_names(&self) -> Vec<&'static str>
+	{
+		iter_chain(self).map(type_name_of).collect()
+	}
+
+	/// Moves the cause out of the error, leaving it without one.
+	///
+	/// ```
+	/// use easy_error::{err_msg, Error};
+	///
+	/// let mut err = Error::new("Request failed", err_msg("Timed out"));
+	/// let cause = err.take_cause().unwrap();
+	///
+	/// assert!(cause.to_string().starts_with("Timed out"));
+	/// assert!(err.cause().is_none());
+	/// ```
+	pub fn take_cause(&mut self) -> Option<Box<dyn error::Error + Send + 'static>>
+	{
+		self.cause.take()
+	}
+
+	/// Adds some context to the error in place.
+	///
+	/// This is the same as `ResultExt::context`, but for an error held in a
+	/// variable: the current error becomes the cause of a new one with the
+	/// given message and the location of the caller.
+	///
+	/// ```
+	/// use easy_error::{err_msg, ErrorExt};
+	///
+	/// let mut err = err_msg("Timed out");
+	/// err.push_context("Request failed");
+	///
+	/// assert_eq!(err.ctx(), "Request failed");
+	/// assert_eq!(err.depth(), 2);
+	/// ```
+	#[allow(clippy::needless_pass_by_value)] // `T: ToString` implies `&T: ToString`
+	#[cfg_attr(not(feature = "no-location"), track_caller)]
+	pub fn push_context<S: ToString>(&mut self, ctx: S)
+	{
+		let outer = Error::from_parts(ctx.to_string(), location::caller(), None);
+		let inner = std::mem::replace(self, outer);
+		self.cause = Some(Box::new(inner));
+	}
+
+	/// Takes the cause out of the error if it is of type `T`.
+	///
+	/// Only the direct cause is checked. If it is a `T`, it is returned and the
+	/// rest of this error is dropped. Otherwise, the error is returned
+	/// unchanged.
+	///
+	/// ```
+	/// use std::num::ParseIntError;
+	/// use easy_error::ResultExt;
+	///
+	/// let err = "abc".parse::<i32>().context("Invalid number").unwrap_err();
+	/// let err = err.downcast::<std::io::Error>().unwrap_err();
+	/// let cause: ParseIntError = err.downcast().unwrap();
+	///
+	/// assert_eq!(cause.to_string(), "invalid digit found in string");
+	/// ```
+	pub fn downcast<T: error::Error + 'static>(mut self) -> std::result::Result<T, Error>
+	{
+		let cause = match self.cause.take() {
+			Some(cause) => cause,
+			None => return Err(self),
+		};
+
+		match cause.downcast::<T>() {
+			Ok(cause) => Ok(*cause),
+			Err(cause) => {
+				self.cause = Some(cause);
+				Err(self)
+			},
+		}
+	}
+
+	/// Transforms the message of the error.
+	///
+	/// The location, cause and every other part of the error are left as they
+	/// are. This is useful for redacting or translating messages after the
+	/// error was created.
+	///
+	/// ```
+	/// let err = easy_error::err_msg("Could not read /home/user/app.toml");
+	/// let err = err.map_message(|ctx| ctx.replace("/home/user", "~"));
+	///
+	/// assert_eq!(err.ctx(), "Could not read ~/app.toml");
+	/// ```
+	pub fn map_message<F: FnOnce(String) -> String>(mut self, f: F) -> Error
+	{
+		self.ctx = Cow::Owned(f(self.ctx.into_owned()));
+		self
+	}
+
+	/// Removes the cause of the error.
+	///
+	/// The message, location, fields and additional errors are kept, and no
+	/// new layer is added. This is useful for hiding internal details before an
+	/// error crosses a trust boundary.
+	///
+	/// ```
+	/// use easy_error::{err_msg, Error, ErrorExt};
+	///
+	/// let err = Error::new("Login failed", err_msg("Password hash mismatch")).without_cause();
+	///
+	/// assert_eq!(err.ctx(), "Login failed");
+	/// assert!(!err.chain_contains("hash"));
+	/// ```
+	pub fn without_cause(mut self) -> Error
+	{
+		self.cause = None;
+		self
+	}
+
+	/// Records another error that occurred alongside this one.
+	///
+	/// This error stays the primary one and provides the chain of causes,
+	/// while `other` is added to the end of `Error::also`. Any errors that `other` had
+	/// recorded itself are moved after it, so the list stays in the order the
+	/// errors were added. A `Terminator` prints these errors after the chain,
+	/// under an "Additionally:" heading.
+	///
+	/// ```
+	/// use easy_error::{err_msg, Terminator};
+	///
+	/// let err = err_msg("Could not read ./app.toml").or_also(err_msg("Could not read ~/app.toml"));
+	/// let output = Terminator::from(err).rendered();
+	///
+	/// assert!(output.contains("Additionally: Could not read ~/app.toml"));
+	/// ```
+	pub fn or_also(mut self, mut other: Error) -> Error
+	{
+		let nested = other.extra.as_mut().map(|e| std::mem::take(&mut e.also)).unwrap_or_default();
+		let also = &mut self.extra_mut().also;
+		also.push(other);
+		also.extend(nested);
+		self
+	}
+
+	/// Adds a key-value field to the error, replacing any previous value.
+	///
+	/// ```
+	/// let err = easy_error::err_msg("Request failed").with_field("status", 503);
+	/// assert_eq!(err.field("status"), Some("503"));
+	/// ```
+	#[allow(clippy::needless_pass_by_value)] // `T: ToString` implies `&T: ToString`
+	pub fn with_field<K: ToString, V: ToString>(mut self, key: K, value: V) -> Error
+	{
+		self.fields_mut().insert(key.to_string(), value.to_string());
+		self
+	}
+
+	/// Copies the fields of another error into this one.
+	///
+	/// This is meant for keeping the fields of an error when it is wrapped in
+	/// a new one. When both errors have a field with the same key, the value
+	/// of this error is kept, since it describes the outer, more specific
+	/// context.
+	///
+	/// ```
+	/// use easy_error::err_msg;
+	///
+	/// let inner = err_msg("Timed out").with_field("host", "db1").with_field("attempt", 3);
+	/// let outer = err_msg("Query failed").with_field("attempt", 4).inherit_fields(&inner);
+	///
+	/// assert_eq!(outer.field("host"), Some("db1"));
+	/// assert_eq!(outer.field("attempt"), Some("4"));
+	/// ```
+	pub fn inherit_fields(mut self, from: &Error) -> Error
+	{
+		for (key, value) in from.fields() {
+			self.fields_mut().entry(key.to_string()).or_insert_with(|| value.to_string());
+		}
+		self
+	}
+
+	/// Adds a link to documentation about the error.
+	///
+	/// A `Terminator` prints the link as `See: <url>` after the layer of the
+	/// chain that it belongs to.
+	///
+	/// ```
+	/// use easy_error::{err_msg, Error, Terminator};
+	///
+	/// let cause = err_msg("Unknown key 'colour'").help_url("https://example.com/config");
+	/// let output = Terminator::from(Error::new("Bad config", cause)).rendered();
+	///
+	/// assert_eq!(output.matches("See: https://example.com/config").count(), 1);
+	/// ```
+	#[allow(clippy::needless_pass_by_value)] // `T: ToString` implies `&T: ToString`
+	pub fn help_url<S: ToString>(mut self, url: S) -> Error
+	{
+		self.extra_mut().url = Some(url.to_string());
+		self
+	}
+
+	/// Records the current values of the given environment variables.
+	///
+	/// Each variable is stored as a field under its own name. The values are
+	/// read when this is called, and variables that are not set are skipped.
+	///
+	/// ```
+	/// std::env::set_var("APP_MODE", "debug");
+	/// let err = easy_error::err_msg("Startup failed").capture_env(&["APP_MODE", "APP_UNSET"]);
+	///
+	/// assert_eq!(err.field("APP_MODE"), Some("debug"));
+	/// assert_eq!(err.field("APP_UNSET"), None);
+	/// ```
+	#[cfg(feature = "capture-env")]
+	pub fn capture_env(mut self, names: &[&str]) -> Error
+	{
+		for &name in names {
+			if let Some(value) = env::var_os(name) {
+				self.fields_mut().insert(name.to_string(), value.to_string_lossy().into_owned());
+			}
+		}
+
+		self
+	}
+
+	/// Creates a chain of errors without causes from a list of messages.
+	///
+	/// The first message is the outermost error and the last one is the root
+	/// cause. Every link gets the location of the caller. An empty list creates
+	/// a single error with an empty message.
+	///
+	/// ```
+	/// use easy_error::{Error, ErrorExt};
+	///
+	/// let err = Error::chain_from(&["Sync failed", "Upload failed", "Timed out"]);
+	///
+	/// assert_eq!(err.ctx(), "Sync failed");
+	/// assert_eq!(err.summary(), "Sync failed: Timed out");
+	/// assert_eq!(err.depth(), 3);
+	/// ```
+	#[cfg_attr(not(feature = "no-location"), track_caller)]
+	pub fn chain_from(messages: &[&str]) -> Error
+	{
+		let location = location::caller();
+		let mut messages = messages.iter().rev().map(|&m| String::from(m));
+
+		let root = Error::from_parts(messages.next().unwrap_or_default(), location, None);
+		messages.fold(root, |cause, ctx| Error::from_parts(ctx, location, Some(Box::new(cause))))
+	}
+
+	/// Create a new error whose chain is made of the given causes.
+	///
+	/// The first cause is the direct cause of the error, the second is the
+	/// cause of the first, and so on. This is useful for rebuilding a chain that
+	/// is only available as a flat list. The causes of the given errors
+	/// themselves are not part of the chain, except for those of the last one.
+	///
+	/// Every cause except the last is wrapped in a private type to link it to
+	/// the next, so those causes cannot be found with `downcast_ref` or
+	/// `ErrorExt::find_cause`. A `Terminator` still prints the location and
+	/// help of an `Error` among them.
+	///
+	/// ```
+	/// use std::error::Error as StdError;
+	/// use easy_error::{Error, ErrorExt};
+	///
+	/// let boxed = |msg: &str| -> Box<dyn StdError + Send> { Box::<dyn StdError + Send + Sync>::from(msg) };
+	/// let err = Error::with_cause_chain("Sync failed", vec![boxed("Upload failed"), boxed("Timed out")]);
+	/// let chain: Vec<_> = err.iter_causes().map(|e| e.to_string()).collect();
+	///
+	/// assert_eq!(chain, ["Upload failed", "Timed out"]);
+	/// ```
+	#[allow(clippy::needless_pass_by_value)] // `T: ToString` implies `&T: ToString`
+	#[cfg_attr(not(feature = "no-location"), track_caller)]
+	pub fn with_cause_chain<S, I>(ctx: S, causes: I) -> Error
+	where
+		S: ToString,
+		I: IntoIterator<Item = Box<dyn error::Error + Send + 'static>>,
+	{
+		let location = location::caller();
+		let mut causes: Vec<_> = causes.into_iter().collect();
+
+		let root = causes.pop();
+		let cause = causes.into_iter().rev().fold(root, |next, error| {
+			Some(Box::new(Nested { error, next }) as Box<dyn error::Error + Send + 'static>)
+		});
+		Error::from_parts(ctx.to_string(), location, cause)
+	}
+
+	/// Combines several errors into a single error.
+	///
+	/// The resulting error's message states how many errors occurred, in the
+	/// same words as the `Display` output of a `MultiError`. Its
+	/// chain then contains one link for each of the errors, in the order they
+	/// were given, so that each is listed as a cause. Only the messages of the
+	/// combined errors are part of the chain, not their own causes.
+	///
+	/// ```
+	/// use easy_error::{err_msg, Error, ErrorExt};
+	///
+	/// let err = Error::wrap_all(vec![err_msg("First failure"), err_msg("Second failure")]);
+	/// let chain: Vec<_> = err.iter_chain().map(|e| e.to_string()).collect();
+	///
+	/// assert!(chain[0].starts_with("2 errors occurred"));
+	/// assert!(chain[1].starts_with("Error 1 of 2: First failure"));
+	/// assert!(chain[2].starts_with("Error 2 of 2: Second failure"));
+	/// ```
+	#[cfg_attr(not(feature = "no-location"), track_caller)]
+	pub fn wrap_all(errors: Vec<Error>) -> Error
+	{
+		let total = errors.len();
+		let ctx = match total {
+			0 => String::from("No errors occurred"),
+			1 => String::from("1 error occurred"),
+			n => format!("{} errors occurred", n),
+		};
+
+		let mut next = None;
+		for (index, error) in errors.into_iter().enumerate().rev() {
+			next = Some(Box::new(Listed { index, total, error, next }));
+		}
+
+		let cause = next.map(|l| l as Box<dyn error::Error + Send + 'static>);
+		Error::from_parts(ctx, location::caller(), cause)
+	}
+
+	/// Writes the message and location, followed by the causes if `chain` is
+	/// set.
+	fn write_text(&self, out: &mut dyn fmt::Write, chain: bool) -> fmt::Result
+	{
+		write!(out, "{}", self.ctx)?;
+		#[cfg(not(feature = "no-location"))]
+		if let Some(origin) = self.origin() {
+			write!(out, " ({})", origin)?;
+		}
+		if chain {
+			for cause in iter_causes(self) {
+				write!(out, ": {}", cause)?;
+			}
+		}
+		Ok(())
 	}
 }
 
 impl Display for Error
 {
+	/// Writes the message of the error followed by its location.
+	///
+	/// With the alternate flag, `{:#}`, each cause is written after it,
+	/// separated by `": "`. The width, fill, alignment and precision of the
+	/// formatter are applied to the text as a whole.
+	///
+	/// ```
+	/// use easy_error::{err_msg, Error};
+	///
+	/// let err = Error::new("Sync failed", err_msg("Bad value"));
+	/// let plain = err.to_string();
+	///
+	/// assert_eq!(format!("{:>60}", err), format!("{:>60}", plain));
+	/// assert_eq!(format!("{:.11}", err), "Sync failed");
+	/// assert!(format!("{:#}", err).starts_with(&format!("{}: Bad value", plain)));
+	/// ```
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result
 	{
-		write!(f, "{} ({})", self.ctx, self.location)
+		let chain = f.alternate();
+		if f.width().is_none() && f.precision().is_none() {
+			return self.write_text(f, chain);
+		}
+
+		let mut text = String::new();
+		self.write_text(&mut text, chain)?;
+		f.pad(&text)
 	}
 }
 
@@ -126,17 +880,326 @@ impl error::Error for Error
 	}
 }
 
+/// Implements `From` for common errors from the standard library.
+///
+/// A blanket implementation for every error type would conflict with the
+/// identity `From` implementation, since `Error` is an error itself.
+macro_rules! impl_from_std
+{
+	($($ty:ty),* $(,)?) => {
+		$(
+			impl From<$ty> for Error
+			{
+				/// Creates an error using the message of the cause as its own.
+				///
+				/// The cause is kept so that it can still be found with
+				/// `ErrorExt::find_cause`, which means its message is shown twice
+				/// in the output of a `Terminator`. Prefer `ResultExt::context` if
+				/// a better message is available.
+				#[cfg_attr(not(feature = "no-location"), track_caller)]
+				fn from(err: $ty) -> Error
+				{
+					Error::from_parts(err.to_string(), location::caller(), Some(Box::new(err)))
+				}
+			}
+		)*
+	};
+}
+
+impl_from_std!(
+	fmt::Error,
+	io::Error,
+	std::char::ParseCharError,
+	std::env::VarError,
+	std::net::AddrParseError,
+	std::num::ParseFloatError,
+	std::num::ParseIntError,
+	std::num::TryFromIntError,
+	std::str::ParseBoolError,
+	std::str::Utf8Error,
+	std::string::FromUtf8Error,
+	std::time::SystemTimeError,
+);
+
+impl FromStr for Error
+{
+	type Err = Infallible;
+
+	/// Creates an error without a cause from the string.
+	///
+	/// Since `str::parse` cannot pass on the location of its caller, the
+	/// location of the error is inside this crate. Use `err_msg` when the
+	/// location matters.
+	///
+	/// ```
+	/// let err: easy_error::Error = "Something broke".parse().unwrap();
+	/// assert_eq!(err.ctx(), "Something broke");
+	/// ```
+	fn from_str(s: &str) -> std::result::Result<Error, Infallible> { Ok(err_msg(s)) }
+}
+
+/// Compares the message of the error with a string.
+///
+/// Only the top message is compared; the location and the cause of the error
+/// are ignored. This is meant for terse assertions in tests.
+///
+/// ```
+/// use easy_error::{ResultExt, err_msg};
+///
+/// let err = "abc".parse::<i32>().context("Invalid number").unwrap_err();
+/// assert_eq!(err, "Invalid number");
+/// assert_ne!(err, "invalid digit found in string");
+/// assert!(err_msg("Failed") == *"Failed");
+/// ```
+impl PartialEq<str> for Error
+{
+	fn eq(&self, other: &str) -> bool { self.ctx == other }
+}
+
+impl PartialEq<&str> for Error
+{
+	fn eq(&self, other: &&str) -> bool { self.ctx == *other }
+}
+
 /// Extension methods to the `Result` type.
 pub trait ResultExt<T>
 {
 	/// Adds some context to the error.
-	#[track_caller]
+	#[cfg_attr(not(feature = "no-location"), track_caller)]
 	fn context<S: ToString>(self, ctx: S) -> Result<T>;
 
+	/// Adds some context to the error, recording the given location instead of
+	/// the caller.
+	///
+	/// This lets functions that wrap `context` attribute the error to their own
+	/// caller.
+	///
+	/// ```
+	/// use std::panic::Location;
+	/// use easy_error::ResultExt;
+	///
+	/// #[track_caller]
+	/// fn parse(s: &str) -> easy_error::Result<i32> {
+	///     s.parse().context_from("Invalid number", Location::caller())
+	/// }
+	///
+	/// let line = line!() + 1;
+	/// let err = parse("abc").unwrap_err();
+	/// assert_eq!(err.location().line(), line);
+	/// ```
+	fn context_from<S: ToString>(self, ctx: S, location: &'static Location<'static>) -> Result<T>;
+
 	/// Adds context to the error, evaluating the context function only if there
 	/// is an `Err`.
-	#[track_caller]
+	#[cfg_attr(not(feature = "no-location"), track_caller)]
 	fn with_context<S: ToString, F: FnOnce() -> S>(self, ctx_fn: F) -> Result<T>;
+
+	/// Merges some context and the error into a single error without a cause.
+	///
+	/// Unlike `context`, which keeps the error as the cause, the chain is
+	/// flattened into the message, which becomes `"<ctx>: <message>"` with the
+	/// messages of every link of the chain joined by `": "`. The locations of
+	/// `Error`s in the chain are left out. This suits tools that only show a
+	/// single line.
+	///
+	/// ```
+	/// use easy_error::{ErrorExt, ResultExt};
+	///
+	/// let err = "abc".parse::<i32>().context_inline("Invalid port").unwrap_err();
+	///
+	/// assert_eq!(err.ctx(), "Invalid port: invalid digit found in string");
+	/// assert_eq!(err.depth(), 1);
+	/// ```
+	#[cfg_attr(not(feature = "no-location"), track_caller)]
+	fn context_inline<S: ToString>(self, ctx: S) -> Result<T>;
+
+	/// Adds a static message as context to the error.
+	///
+	/// The message is borrowed instead of being copied into a new `String`, so
+	/// a message that is used many times is not allocated for each error.
+	///
+	/// ```
+	/// use easy_error::ResultExt;
+	///
+	/// let err = "abc".parse::<i32>().context_static("Invalid number").unwrap_err();
+	/// assert_eq!(err.ctx(), "Invalid number");
+	/// ```
+	#[cfg_attr(not(feature = "no-location"), track_caller)]
+	fn context_static(self, ctx: &'static str) -> Result<T>;
+
+	/// Adds an already owned message as context to the error.
+	///
+	/// Unlike `context`, which calls `to_string` on its argument, the `String`
+	/// is moved into the error without being copied. This is useful when the
+	/// message has been built with `format!`.
+	#[cfg_attr(not(feature = "no-location"), track_caller)]
+	fn context_owned(self, ctx: String) -> Result<T>;
+
+	/// Adds context noting the index of the item that failed.
+	///
+	/// The message is `"<base> [index <index>]"` and is only formatted if
+	/// there is an `Err`.
+	///
+	/// ```
+	/// use easy_error::ResultExt;
+	///
+	/// let items = ["1", "two"];
+	/// let err = items
+	///     .iter()
+	///     .enumerate()
+	///     .map(|(i, s)| s.parse::<i32>().context_indexed("Invalid item", i))
+	///     .collect::<Result<Vec<_>, _>>()
+	///     .unwrap_err();
+	///
+	/// assert_eq!(err.ctx(), "Invalid item [index 1]");
+	/// ```
+	#[cfg_attr(not(feature = "no-location"), track_caller)]
+	fn context_indexed(self, base: &str, index: usize) -> Result<T>;
+
+	/// Adds context noting how much time passed since `start`.
+	///
+	/// The message is `"<ctx> (after <elapsed>)"`, such as `"Request failed
+	/// (after 1.2s)"`. The elapsed time is measured and formatted only if there
+	/// is an `Err`.
+	///
+	/// ```
+	/// use std::time::Instant;
+	/// use easy_error::ResultExt;
+	///
+	/// let start = Instant::now();
+	/// let err = "abc".parse::<i32>().context_timed("Invalid number", start).unwrap_err();
+	///
+	/// assert!(err.ctx().starts_with("Invalid number (after "));
+	/// ```
+	#[cfg_attr(not(feature = "no-location"), track_caller)]
+	fn context_timed<S: ToString>(self, ctx: S, start: Instant) -> Result<T>;
+
+	/// Adds some context to the error along with a cause computed from
+	/// elsewhere.
+	///
+	/// The new cause is placed between the context and the original error, so
+	/// the chain reads: the context, the new cause, then the original error
+	/// and its causes. The context and the cause function are only evaluated
+	/// if there is an `Err`.
+	///
+	/// If the new cause is an `Error`, it is kept as it is along with its own
+	/// causes, and the original error is placed below the last of them. This
+	/// requires every link of the new cause's chain to be an `Error`. Otherwise,
+	/// and for any other type of cause, the new cause is wrapped in a private
+	/// type to link it to the original error, so only its message is kept in
+	/// the chain, not its own causes, and it cannot be found with
+	/// `downcast_ref` or `ErrorExt::find_cause`.
+	///
+	/// ```
+	/// use std::num::ParseIntError;
+	/// use easy_error::{err_msg, Error, ErrorExt, ResultExt};
+	///
+	/// let err = "abc"
+	///     .parse::<i32>()
+	///     .with_cause("Invalid config", || err_msg("The `port` key must be a number"))
+	///     .unwrap_err();
+	/// let chain: Vec<_> = err.iter_chain().map(|e| e.to_string()).collect();
+	///
+	/// assert_eq!(err.ctx(), "Invalid config");
+	/// assert!(chain[1].starts_with("The `port` key must be a number"));
+	/// assert_eq!(chain[2], "invalid digit found in string");
+	/// let cause = err.iter_causes().find_map(|e| e.downcast_ref::<Error>()).unwrap();
+	/// assert_eq!(cause.ctx(), "The `port` key must be a number");
+	/// assert!(err.find_cause::<ParseIntError>().is_some());
+	///
+	/// let err = "abc"
+	///     .parse::<i32>()
+	///     .with_cause("Invalid config", || Error::new("Bad `port` key", err_msg("Not a number")))
+	///     .unwrap_err();
+	/// let chain: Vec<_> = err.iter_chain().map(|e| e.to_string()).collect();
+	///
+	/// assert!(chain[2].starts_with("Not a number"));
+	/// assert_eq!(chain[3], "invalid digit found in string");
+	/// ```
+	#[cfg_attr(not(feature = "no-location"), track_caller)]
+	fn with_cause<S, E2, F>(self, ctx: S, cause_fn: F) -> Result<T>
+	where
+		S: ToString,
+		E2: error::Error + Send + 'static,
+		F: FnOnce() -> E2;
+
+	/// Adds some context to the error and converts it into another error type.
+	///
+	/// This is useful in code whose own error type can be created from an
+	/// `Error`.
+	///
+	/// ```
+	/// use easy_error::ResultExt;
+	///
+	/// #[derive(Debug)]
+	/// struct AppError(easy_error::Error);
+	///
+	/// impl From<easy_error::Error> for AppError {
+	///     fn from(err: easy_error::Error) -> AppError { AppError(err) }
+	/// }
+	///
+	/// fn parse(s: &str) -> Result<i32, AppError> { s.parse().context_into("Invalid number") }
+	///
+	/// assert_eq!(parse("abc").unwrap_err().0.ctx(), "Invalid number");
+	/// ```
+	#[cfg_attr(not(feature = "no-location"), track_caller)]
+	fn context_into<S: ToString, E2: From<Error>>(self, ctx: S) -> std::result::Result<T, E2>;
+
+	/// Boxes the error for APIs that expect a `Box<dyn Error + Send>`.
+	///
+	/// ```
+	/// use easy_error::ResultExt;
+	///
+	/// let result = "abc".parse::<i32>().err_boxed();
+	/// assert_eq!(result.unwrap_err().to_string(), "invalid digit found in string");
+	/// ```
+	fn err_boxed(self) -> std::result::Result<T, Box<dyn error::Error + Send + 'static>>;
+
+	/// Returns the contained value or prints the error and exits the process.
+	///
+	/// On an `Err`, the error and its causes are written to standard error in
+	/// the same format as a `Terminator` and the process exits with a status
+	/// code of `1`. Destructors for values on the stack are not run.
+	///
+	/// ```
+	/// use easy_error::ResultExt;
+	///
+	/// let value: Result<i32, std::num::ParseIntError> = "42".parse();
+	/// assert_eq!(value.unwrap_or_terminate(), 42);
+	/// ```
+	fn unwrap_or_terminate(self) -> T;
+
+	/// Converts the error into a `Terminator`.
+	///
+	/// This is the conversion that `?` performs in a function that returns a
+	/// `Terminator`, for when it cannot be inferred.
+	///
+	/// ```
+	/// use easy_error::{ResultExt, Terminator};
+	///
+	/// fn parse(s: &str) -> Result<i32, Terminator> { s.parse::<i32>().terminate() }
+	///
+	/// assert_eq!(parse("42").unwrap(), 42);
+	/// assert!(parse("abc").is_err());
+	/// ```
+	fn terminate(self) -> std::result::Result<T, Terminator>;
+
+	/// Returns the contained value or logs the error and returns the default.
+	///
+	/// On an `Err`, the error and its causes are logged with `log::error!`,
+	/// along with the location of the call, and `T::default()` is returned.
+	///
+	/// ```
+	/// use easy_error::ResultExt;
+	///
+	/// let value: Result<i32, std::num::ParseIntError> = "abc".parse();
+	/// assert_eq!(value.unwrap_or_default_logged(), 0);
+	/// ```
+	#[cfg(feature = "log")]
+	#[cfg_attr(not(feature = "no-location"), track_caller)]
+	fn unwrap_or_default_logged(self) -> T
+	where
+		T: Default;
 }
 
 impl<T, E> ResultExt<T> for std::result::Result<T, E>
@@ -145,38 +1208,574 @@ where
 {
 	fn context<S: ToString>(self, ctx: S) -> Result<T>
 	{
-		let location = Location::caller();
-		self.map_err(|e| Error { ctx: ctx.to_string(), location, cause: Some(Box::new(e)) })
+		self.context_from(ctx, location::caller())
+	}
+
+	fn context_from<S: ToString>(self, ctx: S, location: &'static Location<'static>) -> Result<T>
+	{
+		self.map_err(|e| Error::from_parts(ctx.to_string(), location, Some(Box::new(e))))
+	}
+
+	fn with_context<S: ToString, F: FnOnce() -> S>(self, ctx_fn: F) -> Result<T>
+	{
+		let location = location::caller();
+		self.map_err(|e| Error::from_parts(ctx_fn().to_string(), location, Some(Box::new(e))))
+	}
+
+	fn context_inline<S: ToString>(self, ctx: S) -> Result<T>
+	{
+		let location = location::caller();
+		self.map_err(|e| {
+			let mut merged = ctx.to_string();
+			for link in iter_chain(&e) {
+				merged.push_str(": ");
+				merged.push_str(&message(link));
+			}
+
+			Error::from_parts(merged, location, None)
+		})
+	}
+
+	fn context_static(self, ctx: &'static str) -> Result<T>
+	{
+		let location = location::caller();
+		self.map_err(|e| Error::from_parts(ctx, location, Some(Box::new(e))))
+	}
+
+	fn context_owned(self, ctx: String) -> Result<T>
+	{
+		let location = location::caller();
+		self.map_err(|e| Error::from_parts(ctx, location, Some(Box::new(e))))
+	}
+
+	fn context_indexed(self, base: &str, index: usize) -> Result<T>
+	{
+		let location = location::caller();
+		self.map_err(|e| {
+			let ctx = format!("{} [index {}]", base, index);
+			Error::from_parts(ctx, location, Some(Box::new(e)))
+		})
+	}
+
+	fn context_timed<S: ToString>(self, ctx: S, start: Instant) -> Result<T>
+	{
+		let location = location::caller();
+		self.map_err(|e| {
+			let ctx = format!("{} (after {:.1?})", ctx.to_string(), start.elapsed());
+			Error::from_parts(ctx, location, Some(Box::new(e)))
+		})
+	}
+
+	fn with_cause<S, E2, F>(self, ctx: S, cause_fn: F) -> Result<T>
+	where
+		S: ToString,
+		E2: error::Error + Send + 'static,
+		F: FnOnce() -> E2,
+	{
+		let location = location::caller();
+		self.map_err(|e| {
+			let cause: Box<dyn error::Error + Send + 'static> = Box::new(cause_fn());
+			let cause: Box<dyn error::Error + Send + 'static> = match cause.downcast::<Error>() {
+				Ok(mut cause) => match cause.attach_root(Box::new(e)) {
+					Ok(()) => cause,
+					Err(original) => Box::new(Nested { error: cause, next: Some(original) }),
+				},
+				Err(error) => Box::new(Nested { error, next: Some(Box::new(e)) }),
+			};
+			Error::from_parts(ctx.to_string(), location, Some(cause))
+		})
+	}
+
+	fn context_into<S: ToString, E2: From<Error>>(self, ctx: S) -> std::result::Result<T, E2>
+	{
+		let location = location::caller();
+		self.map_err(|e| Error::from_parts(ctx.to_string(), location, Some(Box::new(e))).into())
+	}
+
+	fn err_boxed(self) -> std::result::Result<T, Box<dyn error::Error + Send + 'static>>
+	{
+		self.map_err(|e| Box::new(e) as _)
+	}
+
+	fn unwrap_or_terminate(self) -> T
+	{
+		match self {
+			Ok(value) => value,
+			Err(e) => {
+				eprint!("{:?}", Terminator::from(e));
+				std::process::exit(1)
+			},
+		}
+	}
+
+	fn terminate(self) -> std::result::Result<T, Terminator> { self.map_err(Terminator::from) }
+
+	#[cfg(feature = "log")]
+	fn unwrap_or_default_logged(self) -> T
+	where
+		T: Default,
+	{
+		let location = location::caller();
+		self.unwrap_or_else(|e| {
+			let err = Error::from_parts("Using the default value", location, Some(Box::new(e)));
+			log::error!("{:#}", err);
+			T::default()
+		})
+	}
+}
+
+/// Extension methods to the `Option` type.
+pub trait OptionExt<T>
+{
+	/// Converts a `None` into an error with the given message.
+	///
+	/// ```
+	/// use easy_error::OptionExt;
+	///
+	/// let err = None::<i32>.context("No value given").unwrap_err();
+	/// assert_eq!(err.ctx(), "No value given");
+	/// ```
+	#[cfg_attr(not(feature = "no-location"), track_caller)]
+	fn context<S: ToString>(self, ctx: S) -> Result<T>;
+
+	/// Converts a `None` into an error, evaluating the context function only if
+	/// there is no value.
+	///
+	/// ```
+	/// use easy_error::OptionExt;
+	///
+	/// let key = "port";
+	/// let err = None::<i32>.with_context(|| format!("Missing key {:?}", key)).unwrap_err();
+	/// assert_eq!(err.ctx(), "Missing key \"port\"");
+	/// ```
+	#[cfg_attr(not(feature = "no-location"), track_caller)]
+	fn with_context<S: ToString, F: FnOnce() -> S>(self, ctx_fn: F) -> Result<T>;
+
+	/// Returns the contained value or panics with the given message.
+	///
+	/// This is like `Option::expect`; the location of the call is reported as
+	/// the location of the panic.
+	///
+	/// ```should_panic
+	/// use easy_error::OptionExt;
+	///
+	/// let value: Option<i32> = None;
+	/// value.expect_context("A value is required");
+	/// ```
+	#[cfg_attr(not(feature = "no-location"), track_caller)]
+	fn expect_context<S: ToString>(self, ctx: S) -> T;
+}
+
+impl<T> OptionExt<T> for Option<T>
+{
+	fn context<S: ToString>(self, ctx: S) -> Result<T>
+	{
+		let location = location::caller();
+		self.ok_or_else(|| Error::from_parts(ctx.to_string(), location, None))
 	}
 
 	fn with_context<S: ToString, F: FnOnce() -> S>(self, ctx_fn: F) -> Result<T>
 	{
-		let location = Location::caller();
-		self.map_err(|e| Error { ctx: ctx_fn().to_string(), location, cause: Some(Box::new(e)) })
+		let location = location::caller();
+		self.ok_or_else(|| Error::from_parts(ctx_fn().to_string(), location, None))
+	}
+
+	fn expect_context<S: ToString>(self, ctx: S) -> T
+	{
+		// Panicking inside a closure would lose the location of the caller.
+		if let Some(value) = self {
+			return value;
+		}
+
+		panic!("{}", ctx.to_string())
+	}
+}
+
+/// Extension methods to `Result` types that leave the error type unchanged.
+///
+/// These are implemented for every `Result`, regardless of the error type.
+pub trait ResultTapExt<T, E>
+{
+	/// Calls the function with the error, if there is one, and returns the
+	/// `Result` unchanged.
+	///
+	/// ```
+	/// use easy_error::ResultTapExt;
+	///
+	/// let mut failures = 0;
+	/// let result = "abc".parse::<i32>().tap_err(|_| failures += 1);
+	///
+	/// assert!(result.is_err());
+	/// assert_eq!(failures, 1);
+	/// ```
+	fn tap_err<F: FnOnce(&E)>(self, f: F) -> std::result::Result<T, E>;
+}
+
+impl<T, E> ResultTapExt<T, E> for std::result::Result<T, E>
+{
+	fn tap_err<F: FnOnce(&E)>(self, f: F) -> std::result::Result<T, E>
+	{
+		if let Err(e) = &self {
+			f(e);
+		}
+
+		self
+	}
+}
+
+/// Extension methods to `Result` types that depend on the type of the error.
+///
+/// `ResultExt` does not name the error type, so methods that take it as an
+/// argument are kept here.
+pub trait ResultErrExt<T, E>
+{
+	/// Adds some context to the error, but only if it matches the predicate.
+	///
+	/// An error that does not match is converted into an `Error` without an
+	/// extra layer. If it already is an `Error`, it is returned unchanged, and
+	/// otherwise its message is used as the message of the new error, the same
+	/// as the `From` implementations for errors from the standard library.
+	/// Neither the predicate nor the context is evaluated for an `Ok`.
+	///
+	/// ```
+	/// use std::{fs, io};
+	///
+	/// use easy_error::{ErrorExt, ResultErrExt};
+	///
+	/// let err = fs::read("/does/not/exist")
+	///     .context_on(|e| e.kind() == io::ErrorKind::NotFound, "Run `init` to create the file")
+	///     .unwrap_err();
+	/// assert_eq!(err.ctx(), "Run `init` to create the file");
+	///
+	/// let err = fs::read("/does/not/exist")
+	///     .context_on(|e| e.kind() == io::ErrorKind::PermissionDenied, "Check the permissions")
+	///     .unwrap_err();
+	/// assert_eq!(err.depth(), 2);
+	/// assert_ne!(err.ctx(), "Check the permissions");
+	/// ```
+	#[cfg_attr(not(feature = "no-location"), track_caller)]
+	fn context_on<P, S>(self, pred: P, ctx: S) -> Result<T>
+	where
+		P: FnOnce(&E) -> bool,
+		S: ToString;
+}
+
+impl<T, E> ResultErrExt<T, E> for std::result::Result<T, E>
+where
+	E: error::Error + Send + 'static,
+{
+	fn context_on<P, S>(self, pred: P, ctx: S) -> Result<T>
+	where
+		P: FnOnce(&E) -> bool,
+		S: ToString,
+	{
+		let location = location::caller();
+		self.map_err(|e| {
+			if pred(&e) {
+				return Error::from_parts(ctx.to_string(), location, Some(Box::new(e)));
+			}
+
+			let boxed: Box<dyn error::Error + Send + 'static> = Box::new(e);
+			match boxed.downcast::<Error>() {
+				Ok(err) => *err,
+				Err(other) => Error::from_parts(other.to_string(), location, Some(other)),
+			}
+		})
 	}
 }
 
 /// Extension methods to `Error` types.
 pub trait ErrorExt: error::Error
 {
-	fn iter_chain(&self) -> Causes;
+	fn iter_chain(&self) -> Causes<'_>;
 
-	fn iter_causes(&self) -> Causes { Causes { cause: self.iter_chain().nth(1) } }
+	fn iter_causes(&self) -> Causes<'_> { Causes { cause: self.iter_chain().nth(1) } }
 
 	fn find_root_cause(&self) -> &(dyn error::Error + 'static)
 	{
 		self.iter_chain().last().expect("source chain should at least contain original error")
 	}
+
+	/// Iterates over the chain, skipping links that repeat the message of the
+	/// link before them.
+	///
+	/// Messages are compared without the locations of `Error`s from this crate,
+	/// so an error that was wrapped several times with the same context is only
+	/// listed once. The first link of each repeated run is kept, as is the root
+	/// cause.
+	///
+	/// ```
+	/// use easy_error::{err_msg, Error, ErrorExt};
+	///
+	/// let err = Error::new("Retry failed", Error::new("Retry failed", err_msg("Timed out")));
+	/// assert_eq!(err.iter_chain_dedup().count(), 2);
+	/// ```
+	fn iter_chain_dedup(&self) -> DedupCauses<'_>
+	{
+		DedupCauses { inner: self.iter_chain(), last: None }
+	}
+
+	/// Returns the link of the chain at the given depth.
+	///
+	/// A depth of `0` is the error itself, `1` is its direct cause, and so on.
+	/// Returns `None` if the chain is not that long.
+	///
+	/// ```
+	/// use easy_error::{err_msg, Error, ErrorExt};
+	///
+	/// let err = Error::new("Outer", err_msg("Inner"));
+	/// assert!(err.cause_at(1).unwrap().to_string().starts_with("Inner"));
+	/// assert!(err.cause_at(2).is_none());
+	/// ```
+	fn cause_at(&self, depth: usize) -> Option<&(dyn error::Error + 'static)>
+	{
+		self.iter_chain().nth(depth)
+	}
+
+	/// Calls the function with each link of the chain and its depth.
+	///
+	/// The depths are the same as for `cause_at`, starting with `0` for the
+	/// error itself.
+	///
+	/// ```
+	/// use easy_error::{err_msg, Error, ErrorExt};
+	///
+	/// let err = Error::new("Sync failed", err_msg("Timed out"));
+	/// let mut tree = String::new();
+	/// err.for_each_cause(|depth, link| {
+	///     tree.push_str(&"  ".repeat(depth));
+	///     tree.push_str(&link.to_string());
+	///     tree.push('\n');
+	/// });
+	///
+	/// assert!(tree.lines().nth(1).unwrap().starts_with("  Timed out"));
+	/// ```
+	fn for_each_cause<F: FnMut(usize, &(dyn error::Error + 'static))>(&self, mut f: F)
+	{
+		for (depth, link) in self.iter_chain().enumerate() {
+			f(depth, link);
+		}
+	}
+
+	/// Returns the number of links in the chain.
+	///
+	/// An error without a cause has a depth of `1`.
+	///
+	/// ```
+	/// use easy_error::{err_msg, Error, ErrorExt};
+	///
+	/// assert_eq!(err_msg("Timed out").depth(), 1);
+	/// assert_eq!(Error::new("Sync failed", err_msg("Timed out")).depth(), 2);
+	/// ```
+	fn depth(&self) -> usize { self.iter_chain().count() }
+
+	/// Returns the location of the deepest `Error` from this crate in the
+	/// chain.
+	///
+	/// That error is the one closest to where the failure originated, while
+	/// the location of the top error is only where it was last wrapped. Links
+	/// of other types are skipped, and `None` is returned if there are no
+	/// `Error`s in the chain.
+	///
+	/// ```
+	/// use easy_error::{err_msg, Error, ErrorExt};
+	///
+	/// let inner = err_msg("Timed out");
+	/// let origin = inner.location();
+	/// let err = Error::new("Sync failed", inner);
+	///
+	/// let root = err.root_location().unwrap();
+	/// assert_eq!((root.line(), root.column()), (origin.line(), origin.column()));
+	/// assert!("abc".parse::<i32>().unwrap_err().root_location().is_none());
+	/// ```
+	fn root_location(&self) -> Option<&'static Location<'static>>
+	{
+		self.iter_chain().filter_map(|e| e.downcast_ref::<Error>()).last().map(Error::location)
+	}
+
+	/// Returns a one line summary of the error and its root cause.
+	///
+	/// The summary is the message of the error followed by the message of the
+	/// root cause, separated by a colon. If the error has no cause, only its
+	/// message is returned. Locations of `Error`s from this crate are left out.
+	///
+	/// ```
+	/// use easy_error::{err_msg, Error, ErrorExt};
+	///
+	/// let err = Error::new("Sync failed", Error::new("Upload failed", err_msg("Connection timeout")));
+	/// assert_eq!(err.summary(), "Sync failed: Connection timeout");
+	/// assert_eq!(err_msg("Disk full").summary(), "Disk full");
+	/// ```
+	fn summary(&self) -> String
+	{
+		let mut chain = self.iter_chain();
+		let mut summary =
+			message(chain.next().expect("source chain should at least contain original error"));
+		if let Some(root) = chain.last() {
+			summary.push_str(": ");
+			summary.push_str(&message(root));
+		}
+		summary
+	}
+
+	/// Writes every link of the chain to the formatter, separated by `sep`.
+	///
+	/// Each link is written with its `Display` implementation, so `Error`s
+	/// from this crate include their locations. Nothing is allocated, which
+	/// makes this suitable for the `Display` implementations of other types.
+	///
+	/// ```
+	/// use std::fmt;
+	/// use easy_error::{err_msg, Error, ErrorExt};
+	///
+	/// struct Report(Error);
+	///
+	/// impl fmt::Display for Report {
+	///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { self.0.write_chain(f, " -> ") }
+	/// }
+	///
+	/// let report = Report(Error::new("Sync failed", err_msg("Timed out")));
+	/// assert_eq!(report.to_string().matches(" -> ").count(), 1);
+	/// ```
+	fn write_chain(&self, f: &mut Formatter, sep: &str) -> fmt::Result
+	{
+		for (i, link) in self.iter_chain().enumerate() {
+			if i > 0 {
+				f.write_str(sep)?;
+			}
+			write!(f, "{}", link)?;
+		}
+		Ok(())
+	}
+
+	/// Returns whether any link of the chain has a message containing `needle`.
+	///
+	/// The locations of `Error`s from this crate are not part of their
+	/// messages, so they are never matched.
+	///
+	/// ```
+	/// use easy_error::{err_msg, Error, ErrorExt};
+	///
+	/// let err = Error::new("Could not load config", err_msg("Connection timed out"));
+	/// assert!(err.chain_contains("timed out"));
+	/// assert!(!err.chain_contains("refused"));
+	/// assert!(!err.chain_contains("lib.rs"));
+	/// ```
+	fn chain_contains(&self, needle: &str) -> bool
+	{
+		self.iter_chain().any(|e| message(e).contains(needle))
+	}
+
+	/// Returns whether any link of the chain has a message containing `needle`,
+	/// ignoring case.
+	///
+	/// Only ASCII letters are compared without regard to case. Other
+	/// characters, including non-ASCII letters, must match exactly. As with
+	/// `chain_contains`, the locations of `Error`s are not matched.
+	///
+	/// ```
+	/// use easy_error::{err_msg, Error, ErrorExt};
+	///
+	/// let err = Error::new("Could not load config", err_msg("Connection TIMED OUT"));
+	/// assert!(err.chain_contains_ignore_case("timed out"));
+	/// assert!(!err.chain_contains_ignore_case("refused"));
+	/// ```
+	fn chain_contains_ignore_case(&self, needle: &str) -> bool
+	{
+		let needle = needle.to_ascii_lowercase();
+		self.iter_chain().any(|e| message(e).to_ascii_lowercase().contains(&needle))
+	}
+
+	/// Returns whether the messages of the chain match the regular expression.
+	///
+	/// The messages are joined with `": "` into a single line, without the
+	/// locations of `Error`s from this crate, so a pattern can span several
+	/// links.
+	///
+	/// ```
+	/// use regex::Regex;
+	/// use easy_error::{err_msg, Error, ErrorExt};
+	///
+	/// let err = Error::new("Upload failed", err_msg("Timed out after 30s"));
+	/// assert!(err.chain_matches(&Regex::new(r"^Upload failed: .* \d+s$").unwrap()));
+	/// ```
+	#[cfg(feature = "regex")]
+	fn chain_matches(&self, regex: &regex::Regex) -> bool
+	{
+		let messages: Vec<_> = self.iter_chain().map(message).collect();
+		regex.is_match(&messages.join(": "))
+	}
+
+	/// Returns the first link of the chain whose message satisfies the
+	/// predicate.
+	///
+	/// The predicate is given the `Display` output of each link, starting with
+	/// the error itself, and the chain is only walked until a match is found.
+	///
+	/// ```
+	/// use easy_error::{err_msg, Error, ErrorExt};
+	///
+	/// let err = Error::new("Sync failed", Error::new("Upload failed", err_msg("Connection timeout")));
+	/// let link = err.first_cause_where(|msg| msg.contains("timeout")).unwrap();
+	/// assert!(link.to_string().starts_with("Connection timeout"));
+	/// ```
+	fn first_cause_where<P: FnMut(&str) -> bool>(
+		&self,
+		mut pred: P,
+	) -> Option<&(dyn error::Error + 'static)>
+	{
+		self.iter_chain().find(|e| pred(&e.to_string()))
+	}
+
+	/// Returns the first error in the chain that is of type `T`.
+	fn find_cause<T: error::Error + 'static>(&self) -> Option<&T>
+	{
+		self.iter_chain().find_map(|e| e.downcast_ref::<T>())
+	}
+
+	/// Returns whether the chain contains an `io::Error` of the given kind.
+	///
+	/// Every `io::Error` in the chain is checked, not only the first one.
+	///
+	/// ```
+	/// use std::{fs::File, io::ErrorKind};
+	/// use easy_error::{ErrorExt, ResultExt};
+	///
+	/// let err = File::open("does/not/exist.txt").context("Could not open file").unwrap_err();
+	/// assert!(err.caused_by_io_kind(ErrorKind::NotFound));
+	/// ```
+	fn caused_by_io_kind(&self, kind: io::ErrorKind) -> bool
+	{
+		self.iter_chain().filter_map(|e| e.downcast_ref::<io::Error>()).any(|e| e.kind() == kind)
+	}
 }
 
 impl<E: error::Error + 'static> ErrorExt for E
 {
-	fn iter_chain(&self) -> Causes { Causes { cause: Some(self) } }
+	fn iter_chain(&self) -> Causes<'_> { iter_chain(self) }
 }
 
 impl ErrorExt for dyn error::Error
 {
-	fn iter_chain(&self) -> Causes { Causes { cause: Some(self) } }
+	fn iter_chain(&self) -> Causes<'_> { iter_chain(self) }
+}
+
+/// Iterates over the error and all of its causes.
+///
+/// This is the same as `ErrorExt::iter_chain` but can be called directly on an
+/// error trait object.
+pub fn iter_chain<'a>(err: &'a (dyn error::Error + 'static)) -> Causes<'a>
+{
+	Causes { cause: Some(err) }
+}
+
+/// Iterates over the causes of the error, not including the error itself.
+///
+/// This is the same as `ErrorExt::iter_causes` but can be called directly on
+/// an error trait object.
+pub fn iter_causes<'a>(err: &'a (dyn error::Error + 'static)) -> Causes<'a>
+{
+	Causes { cause: err.source() }
 }
 
 /// An iterator over the causes of an error.
@@ -202,11 +1801,255 @@ impl<'a> Iterator for Causes<'a>
 	}
 }
 
+impl FusedIterator for Causes<'_> {}
+
+/// An iterator over the causes of an error that skips repeated messages.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct DedupCauses<'a>
+{
+	/// The iterator over every link of the chain.
+	inner: Causes<'a>,
+
+	/// The message of the previous link.
+	last: Option<String>,
+}
+
+impl<'a> Iterator for DedupCauses<'a>
+{
+	type Item = &'a (dyn error::Error + 'static);
+
+	fn next(&mut self) -> Option<Self::Item>
+	{
+		loop {
+			let link = self.inner.next()?;
+			let message = message(link);
+			let repeated = self.last.as_ref() == Some(&message);
+			self.last = Some(message);
+
+			if !repeated || link.source().is_none() {
+				return Some(link);
+			}
+		}
+	}
+}
+
+impl FusedIterator for DedupCauses<'_> {}
+
+/// Returns the name of the type of a link, if it is one of the known types.
+///
+/// The names are the public paths of the types, which `std::any::type_name`
+/// does not give for types that are defined in private modules.
+fn type_name_of(link: &(dyn error::Error + 'static)) -> &'static str
+{
+	macro_rules! known {
+		($($ty:ty => $name:expr),* $(,)?) => {
+			$(
+				if link.is::<$ty>() {
+					return $name;
+				}
+			)*
+		};
+	}
+
+	known!(
+		Error => "easy_error::Error",
+		MultiError => "easy_error::MultiError",
+		DynError => "easy_error::DynError",
+		fmt::Error => "std::fmt::Error",
+		io::Error => "std::io::Error",
+		std::num::ParseFloatError => "std::num::ParseFloatError",
+		std::num::ParseIntError => "std::num::ParseIntError",
+		std::str::Utf8Error => "std::str::Utf8Error",
+		std::string::FromUtf8Error => "std::string::FromUtf8Error",
+	);
+	"dyn std::error::Error"
+}
+
+/// Returns the message of a link without the location of an `Error`.
+fn message(link: &(dyn error::Error + 'static)) -> String
+{
+	split_location(link).map_or_else(|| link.to_string(), |(message, _)| message.into_owned())
+}
+
+/// Returns the `Error` held by a link of the chain, looking through the links
+/// that this crate creates to chain other errors together.
+fn link_error<'a>(link: &'a (dyn error::Error + 'static)) -> Option<&'a Error>
+{
+	if let Some(listed) = link.downcast_ref::<Listed>() {
+		return Some(&listed.error);
+	}
+	if let Some(nested) = link.downcast_ref::<Nested>() {
+		return nested.error.downcast_ref::<Error>();
+	}
+	link.downcast_ref::<Error>()
+}
+
+/// Returns the message of a link that holds an `Error`, without the location
+/// of the `Error`, along with the `Error` itself.
+fn split_location<'a>(link: &'a (dyn error::Error + 'static)) -> Option<(Cow<'a, str>, &'a Error)>
+{
+	let error = link_error(link)?;
+	let message = link.downcast_ref::<Listed>().map_or(Cow::Borrowed(&*error.ctx), |listed| {
+		Cow::Owned(format!("Error {} of {}: {}", listed.index + 1, listed.total, error.ctx))
+	});
+	Some((message, error))
+}
+
 /// Creates an error message from the provided string.
 #[inline]
 #[allow(clippy::needless_pass_by_value)] // `T: ToString` implies `&T: ToString`
-#[track_caller]
+#[cfg_attr(not(feature = "no-location"), track_caller)]
 pub fn err_msg<S: ToString>(ctx: S) -> Error
 {
-	Error { ctx: ctx.to_string(), location: Location::caller(), cause: None }
+	Error::from_parts(ctx.to_string(), location::caller(), None)
+}
+
+/// Creates an error message from preformatted arguments.
+///
+/// This is the function used by `format_err!` and its related macros, and is
+/// meant for other macros that produce `fmt::Arguments` with `format_args!`.
+///
+/// ```
+/// let err = easy_error::err_msg_args(format_args!("Value {} is too large", 300));
+/// assert_eq!(err.ctx(), "Value 300 is too large");
+/// ```
+#[inline]
+#[cfg_attr(not(feature = "no-location"), track_caller)]
+pub fn err_msg_args(args: fmt::Arguments) -> Error
+{
+	Error::from_parts(fmt::format(args), location::caller(), None)
+}
+
+/// Collects the values of the results, stopping at the first error.
+///
+/// The first error is wrapped with a message noting its zero-based index, as
+/// `"Error at index <index>"`. Unlike a `MultiError`, the remaining results
+/// are not looked at.
+///
+/// ```
+/// use easy_error::{collect_indexed, ResultExt};
+///
+/// let lines = ["80", "443", "http"];
+/// let ports = collect_indexed(lines.iter().map(|s| s.parse::<u16>().context("Invalid port")));
+///
+/// assert_eq!(ports.unwrap_err().ctx(), "Error at index 2");
+/// ```
+#[cfg_attr(not(feature = "no-location"), track_caller)]
+pub fn collect_indexed<T, I>(iter: I) -> Result<Vec<T>>
+where
+	I: IntoIterator<Item = Result<T>>,
+{
+	let location = location::caller();
+	iter.into_iter()
+		.enumerate()
+		.map(|(index, result)| {
+			result.map_err(|e| {
+				Error::from_parts(format!("Error at index {}", index), location, Some(Box::new(e)))
+			})
+		})
+		.collect()
+}
+
+/// A single link in the chain created by `Error::wrap_all`.
+#[derive(Debug)]
+struct Listed
+{
+	/// The position of the error in the original list.
+	index: usize,
+
+	/// The number of errors in the original list.
+	total: usize,
+
+	/// The error itself.
+	error: Error,
+
+	/// The link holding the following error.
+	next: Option<Box<Listed>>,
+}
+
+impl Display for Listed
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		write!(f, "Error {} of {}: {}", self.index + 1, self.total, self.error)
+	}
+}
+
+impl error::Error for Listed
+{
+	fn source(&self) -> Option<&(dyn error::Error + 'static)>
+	{
+		self.next.as_ref().map(|n| &**n as _)
+	}
+}
+
+/// The parts of an `Error` that are rarely set.
+///
+/// They are kept behind a pointer so that an `Error`, and every `Result` that
+/// holds one, stays small.
+#[derive(Debug, Default)]
+struct Extra
+{
+	/// Additional key-value information about the error.
+	fields: BTreeMap<String, String>,
+
+	/// Other errors that occurred alongside this one, such as failed fallbacks.
+	also: Vec<Error>,
+
+	/// A link to documentation about the error.
+	url: Option<String>,
+
+	/// The messages of the `ContextScope`s that were active when the error was
+	/// created, outermost first.
+	context_stack: Vec<String>,
+
+	/// The path of the function the error was created in, if it was recorded
+	/// by `err_here!`.
+	function: Option<&'static str>,
+
+	/// Whether the error was rebuilt from its serialized form.
+	#[cfg(feature = "serde")]
+	rebuilt: bool,
+
+	/// The original location of a rebuilt error, if it was known.
+	#[cfg(feature = "serde")]
+	remote_location: Option<String>,
+}
+
+/// A link in the chain created by `Error::with_cause_chain` or
+/// `ResultExt::with_cause`.
+#[derive(Debug)]
+struct Nested
+{
+	/// The error of this link.
+	error: Box<dyn error::Error + Send + 'static>,
+
+	/// The following link.
+	next: Option<Box<dyn error::Error + Send + 'static>>,
+}
+
+impl Display for Nested
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result { Display::fmt(&self.error, f) }
+}
+
+impl error::Error for Nested
+{
+	fn source(&self) -> Option<&(dyn error::Error + 'static)>
+	{
+		self.next.as_ref().map(|n| &**n as _)
+	}
+}
+
+/// An error propagated from the block of `with_context_block!`.
+///
+/// The standard library only converts errors into boxes that are also `Sync`,
+/// which `Error` is not.
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct __BlockError(pub Box<dyn error::Error + Send + 'static>);
+
+impl<E: error::Error + Send + 'static> From<E> for __BlockError
+{
+	fn from(err: E) -> __BlockError { __BlockError(Box::new(err)) }
 }
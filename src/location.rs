@@ -0,0 +1,120 @@
+//! Control over how error locations are displayed.
+use std::{
+	fmt::{self, Display, Formatter},
+	panic::Location,
+	sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Returns the location of the caller.
+///
+/// With the `no-location` feature, this always returns the same placeholder
+/// location instead, and functions do not pass on the locations of their
+/// callers.
+#[cfg_attr(not(feature = "no-location"), track_caller)]
+#[inline]
+pub fn caller() -> &'static Location<'static> { Location::caller() }
+
+/// The style currently used for displaying locations.
+static STYLE: AtomicUsize = AtomicUsize::new(LocationStyle::Full as usize);
+
+/// How the file of an error's location is displayed.
+///
+/// The style applies to the `Display` output of `Error` and to the output of
+/// `Terminator`. It can be changed with `set_location_style`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LocationStyle
+{
+	/// The path as it was recorded by the compiler. This is the default.
+	Full,
+
+	/// Only the name of the file, without any of its parent directories.
+	FileName,
+}
+
+/// Sets the style used for displaying locations in the whole program.
+///
+/// ```
+/// use easy_error::{err_msg, set_location_style, LocationStyle};
+///
+/// set_location_style(LocationStyle::FileName);
+/// let err = err_msg("Bad value");
+///
+/// assert!(!err.to_string().contains('/'));
+/// # set_location_style(LocationStyle::Full);
+/// ```
+pub fn set_location_style(style: LocationStyle) { STYLE.store(style as usize, Ordering::Relaxed); }
+
+/// Returns the style currently used for displaying locations.
+#[must_use]
+pub fn location_style() -> LocationStyle
+{
+	if STYLE.load(Ordering::Relaxed) == LocationStyle::FileName as usize {
+		LocationStyle::FileName
+	}
+	else {
+		LocationStyle::Full
+	}
+}
+
+/// Displays a location in the current `LocationStyle`.
+pub struct DisplayLocation<'a>(pub &'a Location<'a>);
+
+impl<'a> DisplayLocation<'a>
+{
+	/// Returns the file of the location, shortened according to the current
+	/// style.
+	pub fn file(&self) -> &'a str
+	{
+		match location_style() {
+			LocationStyle::Full => self.0.file(),
+			LocationStyle::FileName => {
+				self.0.file().rsplit(|c| c == '/' || c == '\\').next().unwrap_or_default()
+			},
+		}
+	}
+}
+
+impl Display for DisplayLocation<'_>
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		write!(f, "{}:{}:{}", self.file(), self.0.line(), self.0.column())
+	}
+}
+
+/// Where an error was created.
+#[derive(Clone, Copy)]
+pub enum Origin<'a>
+{
+	/// A location recorded in this process.
+	Local(&'static Location<'static>),
+
+	/// The location of an error that was rebuilt from its serialized form, as
+	/// it was written by the process that created the error.
+	#[cfg_attr(not(feature = "serde"), allow(dead_code))]
+	Remote(&'a str),
+}
+
+impl<'a> Origin<'a>
+{
+	/// Returns the file of the location. Local locations are shortened
+	/// according to the current style.
+	pub fn file(self) -> &'a str
+	{
+		match self {
+			Origin::Local(location) => DisplayLocation(location).file(),
+			Origin::Remote(location) => location.rsplitn(3, ':').last().unwrap_or(location),
+		}
+	}
+}
+
+impl Display for Origin<'_>
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		match *self {
+			Origin::Local(location) => Display::fmt(&DisplayLocation(location), f),
+			Origin::Remote(location) => f.write_str(location),
+		}
+	}
+}
@@ -1,4 +1,7 @@
 /// Exits a function early with an `Error`.
+///
+/// Inside a closure, this returns from the closure rather than the function
+/// that contains it. Use `throw!` to create the `Err` as an expression instead.
 #[macro_export]
 macro_rules! bail
 {
@@ -7,6 +10,34 @@ macro_rules! bail
 	};
 }
 
+/// Creates an `Err` holding an `Error`, without returning it.
+///
+/// This is `bail!` without the `return`, so it can be used as the value of an
+/// expression, such as the last expression of a closure or a `match` arm. The
+/// error is converted with `Into` into the error type that is expected.
+///
+/// ```
+/// use easy_error::{throw, Error};
+///
+/// let parse = |s: &str| -> Result<u16, Error> {
+///     match s.parse() {
+///         Ok(0) => throw!("Port {} is reserved", 0),
+///         Ok(port) => Ok(port),
+///         Err(_) => throw!("Invalid port {:?}", s),
+///     }
+/// };
+///
+/// assert_eq!(parse("0").unwrap_err().ctx(), "Port 0 is reserved");
+/// assert_eq!(parse("80").unwrap(), 80);
+/// ```
+#[macro_export]
+macro_rules! throw
+{
+	($($arg:tt)*) => {
+		Err($crate::format_err!($($arg)*).into())
+	};
+}
+
 /// Exits a function early with an `Error` if the condition is not satisfied.
 #[macro_export]
 macro_rules! ensure
@@ -18,9 +49,429 @@ macro_rules! ensure
 	};
 }
 
+/// Unwraps an `Option`, or exits a function early with an `Error` if it is
+/// `None`.
+///
+/// The message is given using the standard string interpolation syntax, like
+/// with `bail!`, and is only formatted if the `Option` is `None`.
+///
+/// ```
+/// use easy_error::{unwrap_or_bail, Error};
+///
+/// fn port(args: &[&str]) -> Result<u16, Error> {
+///     let port = unwrap_or_bail!(args.get(1), "Expected {} arguments", 2);
+///     Ok(port.parse().unwrap_or(80))
+/// }
+///
+/// assert_eq!(port(&["app"]).unwrap_err().ctx(), "Expected 2 arguments");
+/// assert_eq!(port(&["app", "8080"]).unwrap(), 8080);
+/// ```
+#[macro_export]
+macro_rules! unwrap_or_bail
+{
+	($opt:expr, $($arg:tt)+) => {
+		match $opt {
+			Some(value) => value,
+			None => {
+				$crate::bail!($($arg)+)
+			},
+		}
+	};
+}
+
+/// Adds an `Error` to a collection if the condition is not satisfied.
+///
+/// Unlike `ensure!`, this does not return, so every failed check can be
+/// gathered before reporting them together with `bail_multi!`. The collection
+/// can be a `Vec<Error>` or a `MultiError`, and the message uses the standard
+/// string interpolation syntax.
+///
+/// ```
+/// use easy_error::{bail_multi, ensure_push, Error};
+///
+/// fn validate(ports: &[u16]) -> Result<(), Error> {
+///     let mut errors = Vec::new();
+///     for (i, &port) in ports.iter().enumerate() {
+///         ensure_push!(errors, port != 0, "Port {} is zero", i);
+///     }
+///
+///     if !errors.is_empty() {
+///         bail_multi!(errors);
+///     }
+///     Ok(())
+/// }
+///
+/// assert_eq!(validate(&[0, 80, 0]).unwrap_err().ctx(), "2 errors occurred");
+/// assert!(validate(&[80]).is_ok());
+/// ```
+#[macro_export]
+macro_rules! ensure_push
+{
+	($errors:expr, $cond:expr, $($arg:tt)+) => {
+		if !($cond) {
+			$errors.push($crate::format_err!($($arg)+));
+		}
+	};
+}
+
+/// Exits a function early with an `Error` combining a collection of errors.
+///
+/// The collection can be a `Vec<Error>` or a `MultiError`, and is turned into
+/// a single error with `MultiError::into_error`.
+#[macro_export]
+macro_rules! bail_multi
+{
+	($errors:expr $(,)?) => {
+		return Err($crate::MultiError::from($errors).into_error().into());
+	};
+}
+
+/// Exits a function early with a custom error if the condition is not
+/// satisfied.
+///
+/// The second argument is a closure that creates the error. It is only called
+/// if the condition fails, and its result is converted with `Into` into the
+/// error type of the function, so that type must implement `From` for it.
+///
+/// ```
+/// use easy_error::ensure_with;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum ConfigError { NoPort }
+///
+/// fn check(port: u16) -> Result<u16, ConfigError> {
+///     ensure_with!(port != 0, || ConfigError::NoPort);
+///     Ok(port)
+/// }
+///
+/// assert_eq!(check(0), Err(ConfigError::NoPort));
+/// ```
+#[macro_export]
+macro_rules! ensure_with
+{
+	($cond:expr, $err_fn:expr $(,)?) => {
+		if !($cond) {
+			return Err(($err_fn)().into());
+		}
+	};
+}
+
 /// Creates an `Error` using the standard string interpolation syntax.
+///
+/// Invoking the macro with no arguments creates an error with an empty
+/// message, which is convenient in macros that forward optional arguments.
+///
+/// ```
+/// use easy_error::format_err;
+///
+/// assert_eq!(format_err!("Port {} is in use", 80).ctx(), "Port 80 is in use");
+/// assert_eq!(format_err!().ctx(), "");
+/// ```
 #[macro_export]
 macro_rules! format_err
 {
-	($($arg:tt)*) => { $crate::err_msg(format_args!($($arg)*)) };
+	() => { $crate::err_msg("") };
+	($($arg:tt)+) => { $crate::err_msg_args(format_args!($($arg)+)) };
+}
+
+/// Creates an `Error` that records the function it was created in.
+///
+/// The message is given like with `format_err!`. The error records the path
+/// of the enclosing function, which is returned by `Error::function`, such as
+/// `my_app::config::load`, which a `Terminator` prints after the location of
+/// the error. A function cannot see the name of its caller the way it can see
+/// its location, so this has to be a macro.
+///
+/// The path is taken from `std::any::type_name` of an item declared inside
+/// the function, so it starts with the `module_path!` of the call and has the
+/// same best-effort format: inside a closure, the path ends with a
+/// `{{closure}}` segment, and the format may change between compiler versions.
+///
+/// ```
+/// use easy_error::err_here;
+///
+/// fn load() -> easy_error::Error { err_here!("Missing key {:?}", "port") }
+///
+/// let err = load();
+/// assert_eq!(err.ctx(), "Missing key \"port\"");
+/// assert!(err.function().unwrap().ends_with("::load"));
+/// ```
+#[macro_export]
+macro_rules! err_here
+{
+	($($arg:tt)*) => {{
+		fn __here() {}
+		fn __name_of<T>(_: T) -> &'static str { ::std::any::type_name::<T>() }
+
+		let name = __name_of(__here);
+		$crate::format_err!($($arg)*).__with_function(name.strip_suffix("::__here").unwrap_or(name))
+	}};
+}
+
+/// Adds formatted context to the error of a `Result`.
+///
+/// The message is only formatted if the `Result` is an `Err`, just like with
+/// `ResultExt::with_context`. Unlike `?`, this does not return from the
+/// function.
+///
+/// ```
+/// use easy_error::with_context_fmt;
+///
+/// let port = "80a";
+/// let err = with_context_fmt!(port.parse::<u16>(), "Invalid port {:?}", port).unwrap_err();
+/// assert_eq!(err.ctx(), "Invalid port \"80a\"");
+/// ```
+#[macro_export]
+macro_rules! with_context_fmt
+{
+	($result:expr, $($arg:tt)+) => {
+		$crate::ResultExt::with_context($result, || format!($($arg)+))
+	};
+}
+
+/// Adds context to every error propagated with `?` inside a block.
+///
+/// The block is run inside a closure and the macro evaluates to a `Result`
+/// holding the value of the block, or the first error it propagated wrapped in
+/// the given context. Any error that is `Send` can be propagated, and it is
+/// kept in the chain with its own type.
+///
+/// ```
+/// use std::num::ParseIntError;
+/// use easy_error::{with_context_block, ErrorExt, ResultExt};
+///
+/// fn setup(host: &str, port: &str, retries: &str) -> easy_error::Result<(String, u16, u8)> {
+///     with_context_block! { "Could not set up connection" => {
+///         let host = host.to_string();
+///         let port = port.parse::<u16>().context("Invalid port")?;
+///         let retries = retries.parse::<u8>()?;
+///         (host, port, retries)
+///     }}
+/// }
+///
+/// let err = setup("localhost", "80a", "3").unwrap_err();
+/// assert_eq!(err.ctx(), "Could not set up connection");
+///
+/// let err = setup("localhost", "80", "-1").unwrap_err();
+/// assert!(err.find_cause::<ParseIntError>().is_some());
+/// assert_eq!(setup("localhost", "80", "3").unwrap(), ("localhost".to_string(), 80, 3));
+/// ```
+///
+/// Since the block is the body of a closure, a `return` inside it only leaves
+/// the block, with the returned value as its result, instead of leaving the
+/// enclosing function. For the same reason, `break` and `continue` cannot
+/// refer to a loop outside of the block:
+///
+/// ```compile_fail
+/// use easy_error::with_context_block;
+///
+/// for port in &["80", "80a"] {
+///     let _ = with_context_block! { "Invalid port" => {
+///         if port.is_empty() {
+///             continue;
+///         }
+///         port.parse::<u16>()?
+///     }};
+/// }
+/// ```
+#[macro_export]
+macro_rules! with_context_block
+{
+	($ctx:expr => $body:block) => {
+		(|| -> ::std::result::Result<_, $crate::__BlockError> { ::std::result::Result::Ok($body) })(
+		)
+		.map_err(|err| $crate::Error::new_boxed($ctx, err.0))
+	};
+}
+
+/// Declares a simple enum error type.
+///
+/// Each variant is given a message template which is used for its `Display`
+/// implementation. Variants may carry named fields, which are passed to the
+/// template in the order they are declared. The generated type implements
+/// `Debug`, `Display` and `std::error::Error`, so it works with
+/// `ResultExt::context` like any other error.
+///
+/// ```
+/// use easy_error::{define_error, ResultExt};
+///
+/// define_error! {
+///     /// Errors produced while reading the configuration.
+///     pub enum ConfigError {
+///         Missing => "The configuration file is missing",
+///         BadLine { line: usize, reason: &'static str } => "Line {} is invalid: {}",
+///     }
+/// }
+///
+/// fn parse() -> Result<(), ConfigError> { Err(ConfigError::BadLine { line: 3, reason: "no value" }) }
+///
+/// let err = parse().context("Could not load configuration").unwrap_err();
+/// assert_eq!(err.cause().unwrap().to_string(), "Line 3 is invalid: no value");
+/// assert_eq!(ConfigError::Missing.to_string(), "The configuration file is missing");
+/// ```
+#[macro_export]
+macro_rules! define_error
+{
+	(
+		$(#[$meta:meta])*
+		$vis:vis enum $name:ident
+		{
+			$(
+				$(#[$var_meta:meta])*
+				$variant:ident $({ $($field:ident : $ty:ty),* $(,)? })? => $msg:literal
+			),* $(,)?
+		}
+	) => {
+		$(#[$meta])*
+		#[derive(Debug)]
+		$vis enum $name
+		{
+			$(
+				$(#[$var_meta])*
+				$variant $({ $($field: $ty),* })?
+			),*
+		}
+
+		impl ::std::fmt::Display for $name
+		{
+			fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result
+			{
+				match self {
+					$(
+						$name::$variant $({ $($field),* })? => {
+							write!(f, $msg $($(, $field)*)?)
+						},
+					)*
+				}
+			}
+		}
+
+		impl ::std::error::Error for $name {}
+	};
+}
+
+/// Implements `std::error::Error` for an enum that wraps `Error`s.
+///
+/// The listed variants must each hold a single error, usually an `Error` from
+/// this crate, which is returned by `source`. The other variants have no
+/// source. This makes `iter_chain` and the `Terminator` output continue
+/// through the enum into the chain of the wrapped error. The enum must
+/// implement `Debug` and `Display` itself.
+///
+/// ```
+/// use std::fmt;
+///
+/// use easy_error::{err_msg, impl_easy_source, Error, ErrorExt};
+///
+/// #[derive(Debug)]
+/// enum AppError {
+///     Config(Error),
+///     Network(Error),
+///     Cancelled,
+/// }
+///
+/// impl fmt::Display for AppError {
+///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+///         match self {
+///             AppError::Config(_) => write!(f, "Invalid configuration"),
+///             AppError::Network(_) => write!(f, "Network failure"),
+///             AppError::Cancelled => write!(f, "Cancelled"),
+///         }
+///     }
+/// }
+///
+/// impl_easy_source!(AppError { Config, Network });
+///
+/// let err = AppError::Network(Error::new("Request failed", err_msg("Timed out")));
+/// assert_eq!(err.depth(), 3);
+/// assert_eq!(AppError::Cancelled.depth(), 1);
+/// ```
+#[macro_export]
+macro_rules! impl_easy_source
+{
+	($name:ident { $($variant:ident),* $(,)? }) => {
+		impl ::std::error::Error for $name
+		{
+			fn source(&self) -> ::std::option::Option<&(dyn ::std::error::Error + 'static)>
+			{
+				match self {
+					$(
+						$name::$variant(err) => ::std::option::Option::Some(err),
+					)*
+					#[allow(unreachable_patterns)]
+					_ => ::std::option::Option::None,
+				}
+			}
+		}
+	};
+}
+
+/// Checks whether an expression is an `Err` whose chain mentions a message.
+///
+/// This is the non-panicking counterpart of `assert_err_context!`. The
+/// expression is only borrowed and must evaluate to a `Result` whose error type
+/// implements `std::error::Error`. With the `regex` feature, a pattern can be
+/// given as `regex = "pattern"` to match it against the messages of the chain
+/// instead, as checked by `ErrorExt::chain_matches`.
+///
+/// ```
+/// use easy_error::{matches_err, ResultExt};
+///
+/// let result = "abc".parse::<i32>().context("Could not parse the port");
+/// assert!(matches_err!(result, "invalid digit"));
+/// assert!(!matches_err!(result, "out of range"));
+/// assert!(!matches_err!("80".parse::<i32>(), "invalid digit"));
+/// ```
+#[macro_export]
+macro_rules! matches_err
+{
+	($expr:expr,regex = $pattern:expr $(,)?) => {
+		match &$expr {
+			::std::result::Result::Ok(_) => false,
+			::std::result::Result::Err(err) => {
+				let regex =
+					$crate::__Regex::new($pattern).expect("the pattern should be a valid regex");
+				$crate::ErrorExt::chain_matches(err, &regex)
+			},
+		}
+	};
+	($expr:expr, $needle:expr $(,)?) => {
+		match &$expr {
+			::std::result::Result::Ok(_) => false,
+			::std::result::Result::Err(err) => $crate::ErrorExt::chain_contains(err, $needle),
+		}
+	};
+}
+
+/// Asserts that an expression is an `Err` whose chain mentions a message.
+///
+/// The expression must evaluate to a `Result` whose error type implements
+/// `std::error::Error`. The assertion passes if any link of the error's chain
+/// contains the given string, as checked by `ErrorExt::chain_contains`.
+/// Otherwise it panics with the full chain.
+///
+/// ```
+/// use easy_error::{assert_err_context, ResultExt};
+///
+/// let result = "abc".parse::<i32>().context("Could not parse the port");
+/// assert_err_context!(result, "parse the port");
+/// assert_err_context!("abc".parse::<i32>(), "invalid digit");
+/// ```
+#[macro_export]
+macro_rules! assert_err_context
+{
+	($expr:expr, $needle:expr $(,)?) => {
+		match $expr {
+			::std::result::Result::Ok(_) => {
+				panic!("expected an error mentioning {:?}, found `Ok`", $needle);
+			},
+			::std::result::Result::Err(err) => {
+				if !$crate::ErrorExt::chain_contains(&err, $needle) {
+					let chain: ::std::vec::Vec<_> =
+						$crate::ErrorExt::iter_chain(&err).map(|e| e.to_string()).collect();
+					panic!("expected an error mentioning {:?}, found {:?}", $needle, chain);
+				}
+			},
+		}
+	};
 }
@@ -0,0 +1,39 @@
+//! Diagnostics for errors with `miette`.
+use std::fmt::Display;
+
+use miette::Diagnostic;
+
+use crate::Error;
+
+/// Reports the documentation link and the additional errors to `miette`.
+///
+/// The URL returned by `Error::url` becomes the URL of the diagnostic, and the
+/// errors returned by `Error::also` become its related diagnostics.
+///
+/// ```
+/// use miette::Diagnostic;
+/// use easy_error::err_msg;
+///
+/// let err = err_msg("Unknown key 'colour'")
+///     .help_url("https://example.com/config")
+///     .or_also(err_msg("Unknown key 'flavour'"));
+///
+/// assert_eq!(Diagnostic::url(&err).unwrap().to_string(), "https://example.com/config");
+/// assert_eq!(Diagnostic::related(&err).unwrap().count(), 1);
+/// ```
+impl Diagnostic for Error
+{
+	fn url<'a>(&'a self) -> Option<Box<dyn Display + 'a>>
+	{
+		Error::url(self).map(|url| Box::new(url) as Box<dyn Display + 'a>)
+	}
+
+	fn related<'a>(&'a self) -> Option<Box<dyn Iterator<Item = &'a dyn Diagnostic> + 'a>>
+	{
+		if self.also().is_empty() {
+			return None;
+		}
+
+		Some(Box::new(self.also().iter().map(|e| e as &dyn Diagnostic)))
+	}
+}
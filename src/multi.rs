@@ -0,0 +1,181 @@
+//! Collections of independent errors.
+use std::{
+	error,
+	fmt::{self, Display, Formatter},
+	iter::FromIterator,
+	slice,
+	vec,
+};
+
+use crate::Error;
+
+/// A collection of errors that occurred independently of each other.
+///
+/// This is useful for operations that keep going after a failure, such as
+/// validating every entry of a file, and want to report all of the failures at
+/// the end. It can be collected from an iterator of errors.
+///
+/// ```
+/// use easy_error::{err_msg, MultiError};
+///
+/// let results = vec![Ok(1), Err(err_msg("Bad entry")), Ok(3), Err(err_msg("Worse entry"))];
+/// let errors: MultiError = results.into_iter().filter_map(Result::err).collect();
+///
+/// assert_eq!(errors.len(), 2);
+/// assert_eq!(errors.to_string(), "2 errors occurred");
+/// ```
+#[derive(Debug, Default)]
+pub struct MultiError
+{
+	/// The collected errors, in the order they were added.
+	errors: Vec<Error>,
+
+	/// The maximum number of errors to keep, if there is one.
+	limit: Option<usize>,
+
+	/// The number of errors that were not kept because of the limit.
+	omitted: usize,
+}
+
+impl MultiError
+{
+	/// Creates an empty collection.
+	#[must_use]
+	pub const fn new() -> MultiError { MultiError { errors: Vec::new(), limit: None, omitted: 0 } }
+
+	/// Creates an empty collection that keeps at most `limit` errors.
+	///
+	/// Errors that are added once the limit is reached are dropped, but are
+	/// still counted. The message then reports the total number of errors and
+	/// how many of them were not kept, which keeps memory use bounded when most
+	/// items of a large input fail.
+	///
+	/// ```
+	/// use easy_error::{err_msg, MultiError};
+	///
+	/// let mut errors = MultiError::with_limit(2);
+	/// for i in 0..5 {
+	///     errors.push(err_msg(format!("Bad entry {}", i)));
+	/// }
+	///
+	/// assert_eq!(errors.len(), 2);
+	/// assert_eq!(errors.omitted(), 3);
+	/// assert_eq!(errors.to_string(), "5 errors occurred, 3 of them not recorded");
+	/// ```
+	#[must_use]
+	pub const fn with_limit(limit: usize) -> MultiError
+	{
+		MultiError { errors: Vec::new(), limit: Some(limit), omitted: 0 }
+	}
+
+	/// Adds an error to the collection.
+	///
+	/// If the collection has a limit and is full, the error is only counted.
+	pub fn push(&mut self, err: Error)
+	{
+		if self.limit.map_or(false, |limit| self.errors.len() >= limit) {
+			self.omitted += 1;
+		}
+		else {
+			self.errors.push(err);
+		}
+	}
+
+	/// Returns the number of errors that were dropped because of the limit.
+	#[must_use]
+	pub const fn omitted(&self) -> usize { self.omitted }
+
+	/// Returns the number of errors in the collection.
+	#[must_use]
+	pub fn len(&self) -> usize { self.errors.len() }
+
+	/// Returns whether the collection has no errors.
+	#[must_use]
+	pub fn is_empty(&self) -> bool { self.errors.is_empty() }
+
+	/// Iterates over the errors in the collection.
+	pub fn iter(&self) -> slice::Iter<'_, Error> { self.errors.iter() }
+
+	/// Converts the collection into a single error that lists each of them.
+	///
+	/// See `Error::wrap_all` for how the resulting chain is structured. The
+	/// message is the same as the `Display` output of the collection, which
+	/// also says if errors were dropped because of the limit.
+	///
+	/// ```
+	/// use easy_error::MultiError;
+	///
+	/// let errors = MultiError::new();
+	/// assert_eq!(errors.to_string(), "No errors occurred");
+	/// assert_eq!(errors.into_error().ctx(), "No errors occurred");
+	/// ```
+	#[cfg_attr(not(feature = "no-location"), track_caller)]
+	pub fn into_error(self) -> Error
+	{
+		if self.omitted == 0 {
+			return Error::wrap_all(self.errors);
+		}
+
+		let ctx = self.to_string();
+		Error::wrap_all(self.errors).map_message(|_| ctx)
+	}
+}
+
+impl Display for MultiError
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		match self.errors.len() + self.omitted {
+			0 => write!(f, "No errors occurred"),
+			1 => write!(f, "1 error occurred"),
+			n => write!(f, "{} errors occurred", n),
+		}?;
+
+		if self.omitted > 0 {
+			write!(f, ", {} of them not recorded", self.omitted)?;
+		}
+
+		Ok(())
+	}
+}
+
+impl error::Error for MultiError {}
+
+impl FromIterator<Error> for MultiError
+{
+	fn from_iter<I: IntoIterator<Item = Error>>(iter: I) -> MultiError
+	{
+		MultiError { errors: iter.into_iter().collect(), limit: None, omitted: 0 }
+	}
+}
+
+impl From<Vec<Error>> for MultiError
+{
+	fn from(errors: Vec<Error>) -> MultiError { MultiError { errors, limit: None, omitted: 0 } }
+}
+
+impl Extend<Error> for MultiError
+{
+	fn extend<I: IntoIterator<Item = Error>>(&mut self, iter: I)
+	{
+		for err in iter {
+			self.push(err);
+		}
+	}
+}
+
+impl IntoIterator for MultiError
+{
+	type IntoIter = vec::IntoIter<Error>;
+	type Item = Error;
+
+	fn into_iter(self) -> Self::IntoIter { self.errors.into_iter() }
+}
+
+impl<'a> IntoIterator for &'a MultiError
+{
+	type IntoIter = slice::Iter<'a, Error>;
+	type Item = &'a Error;
+
+	fn into_iter(self) -> Self::IntoIter { self.errors.iter() }
+}
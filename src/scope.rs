@@ -0,0 +1,79 @@
+//! Scopes that describe what the current thread is doing.
+use std::{cell::RefCell, marker::PhantomData};
+
+thread_local! {
+	/// The messages of the scopes that are active on this thread, outermost first.
+	static SCOPES: RefCell<Vec<String>> = RefCell::new(Vec::new());
+}
+
+/// A guard that describes what the current thread is doing while it is alive.
+///
+/// Every `Error` that is created while a scope is active records the messages
+/// of all active scopes, returned by `Error::context_stack`, and a `Terminator` prints them
+/// after the causes as "While:" lines, the innermost scope first. This gives
+/// an outline of what the program was doing without adding context to every
+/// `Result` on the way.
+///
+/// Scopes belong to the thread that entered them: errors created on other
+/// threads do not see them, and a guard cannot be sent to another thread.
+/// Dropping a guard ends its scope along with any scopes that were entered
+/// after it and are still active.
+///
+/// ```
+/// use easy_error::{err_msg, ContextScope, Terminator};
+///
+/// let err = {
+///     let _scope = ContextScope::enter("loading the config");
+///     let _scope = ContextScope::enter("reading ~/app.toml");
+///     err_msg("File not found")
+/// };
+///
+/// assert_eq!(err.context_stack(), ["loading the config", "reading ~/app.toml"]);
+/// assert!(err_msg("Another error").context_stack().is_empty());
+///
+/// let output = Terminator::from(err).rendered();
+/// assert!(output.ends_with("\nWhile: reading ~/app.toml\nWhile: loading the config\n"));
+/// ```
+#[must_use = "the scope ends as soon as the guard is dropped"]
+#[derive(Debug)]
+pub struct ContextScope
+{
+	/// The number of scopes that were active before this one.
+	depth: usize,
+
+	/// Keeps the guard on the thread whose scopes it changes.
+	_thread: PhantomData<*const ()>,
+}
+
+impl ContextScope
+{
+	/// Enters a scope with the given message.
+	#[allow(clippy::needless_pass_by_value)] // `T: ToString` implies `&T: ToString`
+	pub fn enter<S: ToString>(ctx: S) -> ContextScope
+	{
+		let ctx = ctx.to_string();
+		let depth = SCOPES.with(|scopes| {
+			let mut scopes = scopes.borrow_mut();
+			scopes.push(ctx);
+			scopes.len() - 1
+		});
+
+		ContextScope { depth, _thread: PhantomData }
+	}
+}
+
+impl Drop for ContextScope
+{
+	fn drop(&mut self)
+	{
+		// The thread-local is gone if the guard is dropped while the thread exits, in which case
+		// there is nothing left to clean up.
+		let _ = SCOPES.try_with(|scopes| scopes.borrow_mut().truncate(self.depth));
+	}
+}
+
+/// Returns the messages of the active scopes on this thread, outermost first.
+pub fn snapshot() -> Vec<String>
+{
+	SCOPES.try_with(|scopes| scopes.borrow().clone()).unwrap_or_default()
+}
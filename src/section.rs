@@ -0,0 +1,145 @@
+//! Custom sections at the end of the `Terminator` output.
+use std::{
+	env,
+	error,
+	fmt,
+	sync::{OnceLock, PoisonError, RwLock},
+};
+
+/// The sections that have been registered, in the order of registration.
+#[clippy::msrv = "1.70"]
+static SECTIONS: OnceLock<RwLock<Vec<Box<dyn TerminatorSection>>>> = OnceLock::new();
+
+/// A section that is appended to the output of every `Terminator`.
+///
+/// A section has a title, which is printed on a line of its own, and a body,
+/// whose lines are printed below it with an indent. Sections are added with
+/// `register_section` and printed after the error and all of its causes, in
+/// the order they were registered. A section whose body is empty is left out.
+///
+/// ```
+/// use std::{error, fmt};
+///
+/// use easy_error::{Terminator, TerminatorSection, err_msg};
+///
+/// struct Version;
+///
+/// impl TerminatorSection for Version {
+///     fn title(&self) -> &str {
+///         "Version:"
+///     }
+///
+///     fn write_body(&self, f: &mut dyn fmt::Write, _err: &(dyn error::Error + 'static)) -> fmt::Result {
+///         writeln!(f, "1.2.3")
+///     }
+/// }
+///
+/// easy_error::register_section(Version);
+///
+/// let rendered = Terminator::from(err_msg("Failed to start")).rendered();
+/// assert!(rendered.ends_with("Version:\n  1.2.3\n"));
+/// ```
+pub trait TerminatorSection: Send + Sync
+{
+	/// Returns the title of the section, such as `"Environment:"`.
+	fn title(&self) -> &str;
+
+	/// Writes the body of the section for the error being printed.
+	fn write_body(&self, f: &mut dyn fmt::Write, err: &(dyn error::Error + 'static))
+	-> fmt::Result;
+}
+
+/// Adds a section to the output of every `Terminator`.
+///
+/// See `TerminatorSection` for an example.
+#[clippy::msrv = "1.70"]
+pub fn register_section<S: TerminatorSection + 'static>(section: S)
+{
+	SECTIONS
+		.get_or_init(RwLock::default)
+		.write()
+		.unwrap_or_else(PoisonError::into_inner)
+		.push(Box::new(section));
+}
+
+/// Writes every registered section with a non-empty body.
+#[clippy::msrv = "1.70"]
+#[allow(clippy::uninlined_format_args)] // Matches the rest of the crate
+pub fn write_sections(
+	f: &mut dyn fmt::Write,
+	err: &(dyn error::Error + 'static),
+	mut title: impl FnMut(&mut dyn fmt::Write, &str) -> fmt::Result,
+) -> fmt::Result
+{
+	let sections = match SECTIONS.get() {
+		Some(sections) => sections.read().unwrap_or_else(PoisonError::into_inner),
+		None => return Ok(()),
+	};
+
+	for section in sections.iter() {
+		let mut body = String::new();
+		section.write_body(&mut body, err)?;
+		if body.is_empty() {
+			continue;
+		}
+
+		title(f, section.title())?;
+		writeln!(f)?;
+		for line in body.lines() {
+			writeln!(f, "  {}", line)?;
+		}
+	}
+
+	drop(sections);
+	Ok(())
+}
+
+/// A section that lists the values of some environment variables.
+///
+/// Variables that are not set are left out, and the section is not printed
+/// when none of them are set.
+///
+/// ```
+/// use easy_error::{EnvSection, Terminator, err_msg};
+///
+/// std::env::set_var("APP_MODE", "debug");
+/// easy_error::register_section(EnvSection::new(&["APP_MODE", "APP_UNSET"]));
+///
+/// let rendered = Terminator::from(err_msg("Failed to start")).rendered();
+/// assert!(rendered.ends_with("Environment:\n  APP_MODE=debug\n"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct EnvSection
+{
+	/// The names of the variables to list.
+	vars: Vec<String>,
+}
+
+impl EnvSection
+{
+	/// Creates a section that lists the given variables.
+	pub fn new<I, S>(vars: I) -> EnvSection
+	where
+		I: IntoIterator<Item = S>,
+		S: ToString,
+	{
+		EnvSection { vars: vars.into_iter().map(|v| v.to_string()).collect() }
+	}
+}
+
+impl TerminatorSection for EnvSection
+{
+	fn title(&self) -> &'static str { "Environment:" }
+
+	fn write_body(&self, f: &mut dyn fmt::Write, _err: &(dyn error::Error + 'static))
+	-> fmt::Result
+	{
+		for var in &self.vars {
+			if let Some(value) = env::var_os(var) {
+				writeln!(f, "{}={}", var, value.to_string_lossy())?;
+			}
+		}
+
+		Ok(())
+	}
+}
@@ -0,0 +1,131 @@
+//! Serialization of errors with Serde.
+use std::{error, panic::Location};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg(feature = "no-location")]
+use crate::location::Origin;
+use crate::{Error, iter_causes, split_location};
+
+/// The serialized form of an error.
+#[derive(Serialize, Deserialize)]
+struct Repr
+{
+	message:  String,
+	location: Option<String>,
+	causes:   Vec<CauseRepr>,
+}
+
+/// The serialized form of a single cause of an error.
+#[derive(Serialize, Deserialize)]
+struct CauseRepr
+{
+	message:  String,
+	location: Option<String>,
+}
+
+impl CauseRepr
+{
+	/// Describes a link of the chain, separating out the location of our own
+	/// `Error` types.
+	fn from_link(link: &(dyn error::Error + 'static)) -> CauseRepr
+	{
+		split_location(link).map_or_else(
+			|| CauseRepr { message: link.to_string(), location: None },
+			|(message, err)| CauseRepr { message: message.into_owned(), location: location(err) },
+		)
+	}
+}
+
+/// Returns the location of the error as it is displayed.
+///
+/// The original location of a rebuilt error is always kept, while locations
+/// recorded in this process are left out with `no-location`.
+fn location(err: &Error) -> Option<String>
+{
+	match err.origin() {
+		#[cfg(feature = "no-location")]
+		Some(Origin::Local(_)) => None,
+		origin => origin.map(|origin| origin.to_string()),
+	}
+}
+
+impl Error
+{
+	/// Returns the original location of an error that was rebuilt from its
+	/// serialized form, as it was written by the process that created it.
+	///
+	/// ```
+	/// use easy_error::{err_msg, Error};
+	///
+	/// let err = err_msg("Timed out");
+	/// let json = serde_json::to_string(&err).unwrap();
+	/// let rebuilt: Error = serde_json::from_str(&json).unwrap();
+	///
+	/// assert_eq!(err.remote_location(), None);
+	/// assert_eq!(rebuilt.to_string(), err.to_string());
+	/// # if cfg!(feature = "no-location") { return; }
+	/// assert_eq!(rebuilt.remote_location(), Some(&*format!("{}:{}:{}", file!(), err.location().line(), err.location().column())));
+	/// ```
+	#[must_use]
+	pub fn remote_location(&self) -> Option<&str>
+	{
+		self.extra.as_ref().and_then(|e| e.remote_location.as_deref())
+	}
+}
+
+/// Serializes the error as its message, location and the chain of causes.
+///
+/// The location is written in the style chosen with `set_location_style`, or
+/// as `null` with the `no-location` feature. Errors that were rebuilt by
+/// `Deserialize` write their original location instead. The causes are written as a list
+/// of objects with a `message` and a `location`, which is `null` for causes
+/// that are not an `Error` from this crate.
+impl Serialize for Error
+{
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	{
+		let repr = Repr {
+			message:  self.ctx.to_string(),
+			location: location(self),
+			causes:   iter_causes(self).map(CauseRepr::from_link).collect(),
+		};
+
+		repr.serialize(serializer)
+	}
+}
+
+/// Rebuilds an error from its serialized form.
+///
+/// The error and each of its causes become causeless `Error`s nested in the
+/// original order. The original types of the causes are lost. Since a
+/// location cannot be created at runtime, the original one is kept as a
+/// string, which is returned by `Error::remote_location`. It is used instead
+/// of the location returned by `Error::location`, which is a fixed location
+/// inside this crate, by `Display`, `Terminator` and `Serialize`. Causes that
+/// were written without a location are printed without one.
+impl<'de> Deserialize<'de> for Error
+{
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+	{
+		let repr = Repr::deserialize(deserializer)?;
+		let location = Location::caller();
+
+		let rebuild = |message: String, origin: Option<String>, cause: Option<Error>| {
+			let cause = cause.map(|c| Box::new(c) as Box<dyn error::Error + Send + 'static>);
+			let mut err = Error::from_parts(message, location, cause);
+			let extra = err.extra_mut();
+			extra.rebuilt = true;
+			extra.remote_location = origin;
+			err
+		};
+
+		let cause = repr
+			.causes
+			.into_iter()
+			.rev()
+			.fold(None, |cause, c| Some(rebuild(c.message, c.location, cause)));
+
+		Ok(rebuild(repr.message, repr.location, cause))
+	}
+}
@@ -1,10 +1,27 @@
 //! Types that are useful in combination with the `Termination` trait.
+#[cfg(feature = "color")]
+use std::io::IsTerminal;
+#[cfg(feature = "termination")]
+use std::process::{ExitCode, Termination};
 use std::{
+	borrow::Cow,
+	env,
 	error,
-	fmt::{self, Debug, Formatter},
+	fmt::{self, Debug, Display, Formatter},
+	io,
+	panic::{self, Location},
+	string::ToString,
 };
 
-use crate::ErrorExt;
+use crate::{
+	Error,
+	ErrorExt,
+	iter_causes,
+	iter_chain,
+	link_error,
+	location::{self, DisplayLocation, Origin},
+	split_location,
+};
 
 /// An error that wraps all other error types for a nicer debug output.
 ///
@@ -19,25 +36,1158 @@ use crate::ErrorExt;
 /// such a way as to make the output look nice. Additionally, there is a
 /// `Result` specialization in order to make the `main` function a little
 /// cleaner.
+///
+/// Every layer of the chain that is an `Error` from this crate is printed along
+/// with the location where it was created.
+///
+/// ```
+/// # if cfg!(feature = "no-location") { return; }
+/// use easy_error::{Error, ResultExt, Terminator};
+///
+/// let err = "abc".parse::<i32>().context("Invalid number").unwrap_err();
+/// let line = err.location().line();
+/// let output = Terminator::from(Error::new("Bad config", err)).rendered();
+///
+/// assert!(output.contains(&format!("Caused by: Invalid number ({}:{}:", file!(), line)));
+/// ```
+///
+/// Consecutive causes that would print the same line, such as those of an
+/// error that was wrapped again on every attempt of a retry loop, are printed
+/// once along with the number of repeats. With the `sequence-id` feature,
+/// every error prints a different number, so no causes are grouped.
+///
+/// ```
+/// # if cfg!(feature = "sequence-id") { return; }
+/// use easy_error::{err_msg, Error, Terminator};
+///
+/// let mut err = err_msg("Timed out");
+/// for _ in 0..3 {
+///     err = Error::new("Attempt failed", err);
+/// }
+/// let output = Terminator::from(Error::new("Sync failed", err)).rendered();
+///
+/// assert_eq!(output.matches("Attempt failed").count(), 1);
+/// assert!(output.contains(" (x3)\n"));
+/// ```
 pub struct Terminator
 {
 	inner: Box<dyn error::Error + 'static>,
+	style: Style,
+
+	/// Functions that look for an exit code on a link of the chain.
+	#[cfg(feature = "termination")]
+	exit_codes: Vec<ExitCodeFn>,
+}
+
+/// A function that returns the exit code of a link, if it has one.
+#[cfg(feature = "termination")]
+#[clippy::msrv = "1.61"]
+type ExitCodeFn = fn(&(dyn error::Error + 'static)) -> Option<ExitCode>;
+
+impl Terminator
+{
+	/// Adds a top-level message to the error.
+	///
+	/// The message is printed first, with the previously wrapped error and its
+	/// causes listed below it.
+	///
+	/// ```
+	/// use easy_error::{err_msg, Terminator};
+	///
+	/// let term = Terminator::from(err_msg("Bad value")).context("Program failed");
+	/// let output = term.rendered();
+	///
+	/// assert!(output.starts_with("Program failed"));
+	/// assert!(output.contains("Caused by: Bad value"));
+	/// ```
+	#[allow(clippy::needless_pass_by_value)] // `T: ToString` implies `&T: ToString`
+	#[must_use]
+	#[cfg_attr(not(feature = "no-location"), track_caller)]
+	pub fn context<S: ToString>(self, ctx: S) -> Terminator
+	{
+		self.wrap(ctx.to_string(), location::caller())
+	}
+
+	/// Wraps the current error in a new layer with the given message.
+	fn wrap(self, ctx: String, location: &'static Location<'static>) -> Terminator
+	{
+		Terminator { inner: Box::new(Context { ctx, location, cause: self.inner }), ..self }
+	}
+
+	/// Returns the output that is printed when the `Terminator` is returned from
+	/// `main`.
+	///
+	/// This is the same text as the `Debug` output, except that it has no colors
+	/// unless they are enabled with `force_color`. It is meant for tests that
+	/// check the messages a program reports.
+	///
+	/// # Panics
+	///
+	/// Like `ToString::to_string`, this panics if the `Display` implementation
+	/// of an error in the chain returns an error.
+	///
+	/// ```
+	/// use easy_error::{err_msg, Error, Terminator};
+	///
+	/// let term = Terminator::from(Error::new("Bad config", err_msg("Bad value"))).quiet();
+	/// let rendered = term.rendered();
+	///
+	/// assert!(rendered.starts_with("Bad config"));
+	/// assert!(rendered.contains("\nCaused by: Bad value"));
+	/// ```
+	#[must_use]
+	pub fn rendered(&self) -> String { render_to_string(&*self.inner, self.style) }
+
+	/// Returns the output like `rendered`, but with placeholders for the line
+	/// and column numbers.
+	///
+	/// Locations are printed as `file:LINE:COL`, so snapshots of the output do
+	/// not change when code is moved around in a file.
+	///
+	/// # Panics
+	///
+	/// Like `rendered`, this panics if the `Display` implementation of an error
+	/// in the chain returns an error.
+	///
+	/// ```
+	/// # if cfg!(feature = "no-location") { return; }
+	/// use easy_error::{err_msg, Error, Terminator};
+	///
+	/// let term = Terminator::from(Error::new("Bad config", err_msg("Bad value")));
+	/// let rendered = term.rendered_stable();
+	///
+	/// assert!(rendered.starts_with(&format!("Bad config ({}:LINE:COL)", file!())));
+	/// ```
+	#[must_use]
+	pub fn rendered_stable(&self) -> String
+	{
+		render_to_string(&*self.inner, Style { stable: true, ..self.style })
+	}
+
+	/// Writes the output that is printed when the `Terminator` is returned from
+	/// `main` to a writer.
+	///
+	/// The output is written piece by piece instead of being collected into a
+	/// string first, and the writer is flushed at the end. Like `rendered`, it
+	/// has no colors unless they are enabled with `force_color`.
+	///
+	/// ```
+	/// use easy_error::{err_msg, Error, Terminator};
+	///
+	/// let term = Terminator::from(Error::new("Bad config", err_msg("Bad value")));
+	/// let mut output = Vec::new();
+	/// term.render_to(&mut output).unwrap();
+	///
+	/// assert_eq!(String::from_utf8(output).unwrap(), term.rendered());
+	/// ```
+	pub fn render_to<W: io::Write>(&self, mut writer: W) -> io::Result<()>
+	{
+		let mut adapter = IoWriter { inner: &mut writer, error: None };
+		if render(&mut adapter, &*self.inner, self.style).is_err() {
+			return Err(adapter.error.unwrap_or_else(|| {
+				io::Error::new(io::ErrorKind::Other, "a Display implementation returned an error")
+			}));
+		}
+
+		writer.flush()
+	}
+
+	/// Uses the exit code of errors of type `T` in the chain.
+	///
+	/// The exit code is only used when the `Terminator` is reported with
+	/// `Termination::report`, not when it is the error of a `Result` returned
+	/// from `main`. See `ExitCoded` for an example.
+	#[cfg(feature = "termination")]
+	#[must_use]
+	pub fn exit_code_for<T: ExitCoded + 'static>(mut self) -> Terminator
+	{
+		self.exit_codes.push(|link| link.downcast_ref::<T>().map(ExitCoded::exit_code));
+		self
+	}
+
+	/// Limits the number of causes that are printed for each error.
+	///
+	/// The causes past the limit are summarized in a single line giving their
+	/// number. This keeps the output of very deep chains short.
+	///
+	/// ```
+	/// use easy_error::{Error, Terminator};
+	///
+	/// let err = Error::chain_from(&["Outer", "Middle", "Inner", "Root"]);
+	/// let output = Terminator::from(err).max_causes(1).quiet().rendered();
+	///
+	/// assert_eq!(output.lines().count(), 3);
+	/// assert!(output.ends_with("\n... and 2 more causes\n"));
+	/// ```
+	#[must_use]
+	pub const fn max_causes(mut self, max: usize) -> Terminator
+	{
+		self.style.max_causes = Some(max);
+		self
+	}
+
+	/// Limits the number of characters printed for the message of each error
+	/// and cause.
+	///
+	/// Only the messages count towards the limit. Labels such as "Caused by:"
+	/// and the locations after the messages are always printed in full. A
+	/// message that is longer is cut short and ends with an ellipsis, `…`,
+	/// which counts towards the limit. A limit of zero leaves the messages out
+	/// entirely. Characters are never split, but a character that takes up two
+	/// columns in a terminal still counts as one.
+	///
+	/// ```
+	/// # if cfg!(feature = "no-location") { return; }
+	/// use easy_error::{err_msg, Error, Terminator};
+	///
+	/// let err = Error::new("Bad response", err_msg(format!("Unexpected body: {}", "é".repeat(500))));
+	/// let output = Terminator::from(err).max_line_length(20).rendered();
+	///
+	/// assert!(output.starts_with("Bad response ("));
+	/// assert!(output.contains("Caused by: Unexpected body: éé… ("));
+	/// ```
+	#[must_use]
+	pub const fn max_line_length(mut self, max: usize) -> Terminator
+	{
+		self.style.max_line_length = Some(max);
+		self
+	}
+
+	/// Leaves the locations of errors out of the output.
+	///
+	/// Only the messages and causes are printed, which suits tools meant for end
+	/// users. Setting the `EASY_ERROR_QUIET` environment variable to anything
+	/// other than `0` has the same effect.
+	///
+	/// ```
+	/// use easy_error::{err_msg, Error, Terminator};
+	///
+	/// let term = Terminator::from(Error::new("Bad config", err_msg("Bad value"))).quiet();
+	/// let output = term.rendered();
+	///
+	/// assert!(output.starts_with("Bad config"));
+	/// assert!(!output.contains(file!()));
+	/// ```
+	#[must_use]
+	pub const fn quiet(mut self) -> Terminator
+	{
+		self.style.quiet = true;
+		self
+	}
+
+	/// Skips causes that repeat the message of the link before them.
+	///
+	/// See `ErrorExt::iter_chain_dedup` for how links are compared.
+	///
+	/// ```
+	/// use easy_error::{err_msg, Error, Terminator};
+	///
+	/// let err = Error::new("Retry failed", Error::new("Retry failed", err_msg("Timed out")));
+	/// let output = Terminator::from(err).dedup().rendered();
+	///
+	/// assert_eq!(output.matches("Retry failed").count(), 1);
+	/// ```
+	#[must_use]
+	pub const fn dedup(mut self) -> Terminator
+	{
+		self.style.dedup = true;
+		self
+	}
+
+	/// Enables or disables colored output, overriding the automatic detection.
+	///
+	/// By default, colors are used only when the output is printed to a
+	/// terminal: the `Debug` output, which is printed to standard error when
+	/// the `Terminator` is returned from `main`, the output of `print_error` and
+	/// the panic hook. The `NO_COLOR`, `CLICOLOR_FORCE` and `CLICOLOR`
+	/// environment variables are also honored, in that order of precedence. The
+	/// output of `rendered`, `rendered_stable` and `render_to` is only colored
+	/// if colors are enabled here.
+	///
+	/// ```
+	/// use easy_error::{err_msg, Terminator};
+	///
+	/// let term = Terminator::from(err_msg("Bad value")).force_color(true);
+	/// assert!(format!("{:?}", term).starts_with("\x1b["));
+	///
+	/// let term = Terminator::from(err_msg("Bad value")).force_color(false);
+	/// assert!(!format!("{:?}", term).contains('\x1b'));
+	/// ```
+	#[cfg(feature = "color")]
+	#[must_use]
+	pub const fn force_color(mut self, enabled: bool) -> Terminator
+	{
+		self.style.color = Some(enabled);
+		self
+	}
+
+	/// Replaces the "Caused by:" label that is printed before each cause.
+	///
+	/// ```
+	/// use easy_error::{err_msg, Error, Terminator};
+	///
+	/// let term = Terminator::from(Error::new("Bad config", err_msg("Bad value")));
+	/// let output = term.with_cause_label("Porque:").rendered();
+	///
+	/// assert!(output.contains("\nPorque: Bad value"));
+	/// assert!(!output.contains("Caused by:"));
+	/// ```
+	#[must_use]
+	pub const fn with_cause_label(mut self, label: &'static str) -> Terminator
+	{
+		self.style.cause_label = Some(label);
+		self
+	}
+
+	/// Uses a denser format, with an arrow before each cause.
+	///
+	/// The error is printed after an `error:` prefix and each cause is printed
+	/// on an indented line starting with `↳` instead of "Caused by:". A label
+	/// set with `with_cause_label` still takes the place of the arrow.
+	///
+	/// ```
+	/// use easy_error::{Error, Terminator};
+	///
+	/// let err = Error::chain_from(&["Bad config", "Bad value", "Not a number"]);
+	/// let output = Terminator::from(err).compact().quiet().rendered();
+	///
+	/// assert!(output.starts_with("error: Bad config"));
+	/// assert!(output.contains("\n  ↳ Bad value"));
+	/// assert!(output.contains("\n  ↳ Not a number"));
+	/// ```
+	#[must_use]
+	pub const fn compact(mut self) -> Terminator
+	{
+		self.style.layout = Layout::Compact;
+		self
+	}
+
+	/// Ends every line of the output with `\r\n` instead of `\n`.
+	///
+	/// Line breaks inside the messages of errors are converted as well.
+	///
+	/// ```
+	/// use easy_error::{err_msg, Error, Terminator};
+	///
+	/// let term = Terminator::from(Error::new("Bad config", err_msg("Bad value"))).crlf();
+	/// let output = term.rendered();
+	///
+	/// assert_eq!(output.matches("\r\n").count(), 2);
+	/// assert_eq!(output.matches('\n').count(), 2);
+	/// ```
+	#[must_use]
+	pub const fn crlf(mut self) -> Terminator
+	{
+		self.style.line_ending = LineEnding::CrLf;
+		self
+	}
+}
+
+impl Terminator
+{
+	/// Creates a `Terminator` with the default options.
+	fn from_boxed(inner: Box<dyn error::Error + 'static>) -> Terminator
+	{
+		Terminator {
+			inner,
+			style: Style::default(),
+			#[cfg(feature = "termination")]
+			exit_codes: Vec::new(),
+		}
+	}
+}
+
+/// Extension methods to `Result` types that hold a `Terminator`.
+///
+/// These mirror the methods of `ResultExt`, but keep the error as a
+/// `Terminator` so they can be used in functions that already return one.
+///
+/// ```
+/// use easy_error::{err_msg, Terminator, TerminatorResultExt};
+///
+/// fn setup() -> Result<(), Terminator> { Err(err_msg("Missing file").into()) }
+///
+/// let term = setup().context("Setup failed").unwrap_err();
+/// assert!(term.rendered().starts_with("Setup failed"));
+/// ```
+pub trait TerminatorResultExt<T>
+{
+	/// Adds some context to the error.
+	#[cfg_attr(not(feature = "no-location"), track_caller)]
+	fn context<S: ToString>(self, ctx: S) -> Result<T, Terminator>;
+
+	/// Adds context to the error, evaluating the context function only if there
+	/// is an `Err`.
+	#[cfg_attr(not(feature = "no-location"), track_caller)]
+	fn with_context<S: ToString, F: FnOnce() -> S>(self, ctx_fn: F) -> Result<T, Terminator>;
+}
+
+impl<T> TerminatorResultExt<T> for Result<T, Terminator>
+{
+	fn context<S: ToString>(self, ctx: S) -> Result<T, Terminator>
+	{
+		let location = location::caller();
+		self.map_err(|t| t.wrap(ctx.to_string(), location))
+	}
+
+	fn with_context<S: ToString, F: FnOnce() -> S>(self, ctx_fn: F) -> Result<T, Terminator>
+	{
+		let location = location::caller();
+		self.map_err(|t| t.wrap(ctx_fn().to_string(), location))
+	}
 }
 
 impl Debug for Terminator
 {
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result
 	{
-		writeln!(f, "{}", self.inner)?;
-		for cause in self.inner.iter_causes() {
-			writeln!(f, "Caused by: {}", cause)?;
+		render(f, &*self.inner, self.style.detect_color(Stream::Stderr))
+	}
+}
+
+impl<E: error::Error + 'static> From<E> for Terminator
+{
+	fn from(err: E) -> Terminator { Terminator::from_boxed(Box::new(err)) }
+}
+
+impl From<SendTerminator> for Terminator
+{
+	fn from(term: SendTerminator) -> Terminator { Terminator::from_boxed(term.inner) }
+}
+
+/// A `Terminator` that can be sent to other threads.
+///
+/// A `Terminator` accepts any error, so it is not `Send`. This type only
+/// accepts errors that are `Send`, which lets it be created on one thread, such
+/// as inside an async task, and printed on another. It is printed with the
+/// default options and converts into a `Terminator`, which provides the
+/// other options.
+///
+/// ```
+/// use std::thread;
+///
+/// use easy_error::{err_msg, SendTerminator, Terminator};
+///
+/// let worker = thread::spawn(|| -> Result<(), SendTerminator> { Err(err_msg("Worker failed").into()) });
+/// let term = Terminator::from(worker.join().unwrap().unwrap_err());
+///
+/// assert!(term.quiet().rendered().starts_with("Worker failed"));
+/// ```
+pub struct SendTerminator
+{
+	inner: Box<dyn error::Error + Send + 'static>,
+}
+
+impl Debug for SendTerminator
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		render(f, &*self.inner, Style::default().detect_color(Stream::Stderr))
+	}
+}
+
+impl<E: error::Error + Send + 'static> From<E> for SendTerminator
+{
+	fn from(err: E) -> SendTerminator { SendTerminator { inner: Box::new(err) } }
+}
+
+/// Prints the error to stderr the same way a `Terminator` does.
+///
+/// The standard library formats the error of a `Result` returned from `main`
+/// with `Debug`, so this is used when the `Error` itself is the outcome of the
+/// program, such as when `main` returns `ExitCode` or `impl Termination`.
+///
+/// ```no_run
+/// use std::process::{ExitCode, Termination};
+/// use easy_error::{bail, Error};
+///
+/// fn run() -> Result<(), Error> { bail!("Nothing to do") }
+///
+/// fn main() -> ExitCode {
+///     match run() {
+///         Ok(()) => ExitCode::SUCCESS,
+///         Err(e) => e.report(),
+///     }
+/// }
+/// ```
+#[cfg(feature = "termination")]
+#[clippy::msrv = "1.61"]
+impl Termination for Error
+{
+	fn report(self) -> ExitCode { Terminator::from(self).report() }
+}
+
+/// Prints the error to stderr and returns the exit code of the chain.
+///
+/// The exit code is the one of the first link of the chain with a type that
+/// was registered with `Terminator::exit_code_for`, or `ExitCode::FAILURE` if
+/// there is none. This is not called when `main` returns
+/// `Result<(), Terminator>`; see `ExitCoded` for how to use it.
+#[cfg(feature = "termination")]
+#[clippy::msrv = "1.61"]
+impl Termination for Terminator
+{
+	#[allow(clippy::uninlined_format_args)] // Matches the rest of the crate
+	fn report(self) -> ExitCode
+	{
+		eprint!("{:?}", self);
+
+		let code =
+			iter_chain(&*self.inner).find_map(|link| self.exit_codes.iter().find_map(|f| f(link)));
+		code.unwrap_or(ExitCode::FAILURE)
+	}
+}
+
+/// An error that decides the exit code of the process.
+///
+/// The default exit code is `ExitCode::FAILURE`. The code is only used in two
+/// cases, and it is easy to miss both:
+///
+/// * The `Terminator` has to be reported through its own `Termination`
+///   implementation. When `main` returns `Result<(), Terminator>`, the
+///   standard library prints the `Terminator` with `Debug` and always exits
+///   with `ExitCode::FAILURE`, without ever consulting `ExitCoded`.
+/// * The type has to be registered with `Terminator::exit_code_for`. The types
+///   in a chain are not known otherwise, so a type that implements `ExitCoded`
+///   but was not registered is ignored.
+///
+/// To use the exit code, return `ExitCode` from `main` and report the
+/// `Terminator` yourself:
+///
+/// ```no_run
+/// use std::process::{ExitCode, Termination};
+/// use easy_error::{define_error, ExitCoded, Terminator};
+///
+/// define_error! {
+///     enum UsageError {
+///         MissingArgument => "An argument is missing",
+///     }
+/// }
+///
+/// impl ExitCoded for UsageError {
+///     fn exit_code(&self) -> ExitCode { ExitCode::from(2) }
+/// }
+///
+/// fn run() -> Result<(), Terminator> { Err(UsageError::MissingArgument.into()) }
+///
+/// fn main() -> ExitCode {
+///     match run() {
+///         Ok(()) => ExitCode::SUCCESS,
+///         Err(t) => t.exit_code_for::<UsageError>().report(),
+///     }
+/// }
+/// ```
+///
+/// The code is looked up in every link of the chain:
+///
+/// ```
+/// use std::process::{ExitCode, Termination};
+/// use easy_error::{define_error, ExitCoded, ResultExt, Terminator};
+///
+/// define_error! {
+///     enum UsageError {
+///         MissingArgument => "An argument is missing",
+///     }
+/// }
+///
+/// impl ExitCoded for UsageError {
+///     fn exit_code(&self) -> ExitCode { ExitCode::from(2) }
+/// }
+///
+/// let result: Result<(), UsageError> = Err(UsageError::MissingArgument);
+/// let term = Terminator::from(result.context("Invalid usage").unwrap_err());
+///
+/// assert_eq!(term.exit_code_for::<UsageError>().report(), ExitCode::from(2));
+/// ```
+#[cfg(feature = "termination")]
+#[clippy::msrv = "1.61"]
+pub trait ExitCoded: error::Error
+{
+	/// Returns the exit code for the error.
+	fn exit_code(&self) -> ExitCode { ExitCode::FAILURE }
+}
+
+/// Options controlling how a `Terminator` is rendered.
+#[derive(Clone, Copy, Debug, Default)]
+struct Style
+{
+	/// Whether to use colors, or `None` to detect it from the environment.
+	#[cfg(feature = "color")]
+	color: Option<bool>,
+
+	/// Whether to leave out the locations of errors.
+	quiet: bool,
+
+	/// Whether to skip causes that repeat the message before them.
+	dedup: bool,
+
+	/// The label printed before each cause, or `None` for the default.
+	cause_label: Option<&'static str>,
+
+	/// Whether to print placeholders instead of line and column numbers.
+	stable: bool,
+
+	/// The maximum number of causes to print for each error, if there is one.
+	max_causes: Option<usize>,
+
+	/// The maximum number of characters of each error's line, if there is one.
+	max_line_length: Option<usize>,
+
+	/// The format of the error and its causes.
+	layout: Layout,
+
+	/// The characters that end each line.
+	line_ending: LineEnding,
+}
+
+impl Style
+{
+	/// Returns whether the output should be colored.
+	///
+	/// Colors are only used if they were forced or detected for a stream, so
+	/// output that is not printed to a stream has none.
+	#[cfg(feature = "color")]
+	fn color(self) -> bool { self.color.unwrap_or(false) }
+
+	/// Returns whether the output should be colored.
+	#[cfg(not(feature = "color"))]
+	#[allow(clippy::unused_self)] // Matches the signature used with colors enabled
+	const fn color(self) -> bool { false }
+
+	/// Detects whether to use colors for output to the stream, unless they are
+	/// forced.
+	#[cfg(feature = "color")]
+	#[clippy::msrv = "1.70"]
+	fn detect_color(mut self, stream: Stream) -> Style
+	{
+		if self.color.is_none() {
+			let is_terminal = match stream {
+				Stream::Stdout => io::stdout().is_terminal(),
+				Stream::Stderr => io::stderr().is_terminal(),
+			};
+			self.color = Some(use_color(is_terminal));
+		}
+		self
+	}
+
+	/// Detects whether to use colors for output to the stream, unless they are
+	/// forced.
+	#[cfg(not(feature = "color"))]
+	#[allow(clippy::missing_const_for_fn)] // Matches the signature used with colors enabled
+	fn detect_color(self, _stream: Stream) -> Style { self }
+
+	/// Returns the label printed before each cause.
+	fn cause_label(self) -> &'static str
+	{
+		self.cause_label.unwrap_or(match self.layout {
+			Layout::Standard => "Caused by:",
+			Layout::Compact => "  ↳",
+		})
+	}
+
+	/// Returns whether the locations of errors should be left out.
+	fn quiet(self) -> bool
+	{
+		self.quiet || env::var_os("EASY_ERROR_QUIET").map_or(false, |v| !v.is_empty() && v != "0")
+	}
+
+	/// Returns how the locations of errors should be printed.
+	fn locations(self) -> Locations
+	{
+		if cfg!(feature = "no-location") || self.quiet() {
+			Locations::Hidden
+		}
+		else if self.stable {
+			Locations::Placeholders
+		}
+		else {
+			Locations::Shown
+		}
+	}
+}
+
+/// The format of the error and its causes.
+#[derive(Clone, Copy, Debug)]
+enum Layout
+{
+	/// Each cause is printed after a "Caused by:" label.
+	Standard,
+
+	/// The error is printed after an `error:` prefix and each cause after an
+	/// indented arrow.
+	Compact,
+}
+
+impl Default for Layout
+{
+	fn default() -> Layout { Layout::Standard }
+}
+
+/// The characters that end each line of the output.
+#[derive(Clone, Copy, Debug)]
+enum LineEnding
+{
+	/// A line feed, `\n`.
+	Lf,
+
+	/// A carriage return and a line feed, `\r\n`.
+	CrLf,
+}
+
+impl Default for LineEnding
+{
+	fn default() -> LineEnding { LineEnding::Lf }
+}
+
+/// Decides whether colors should be used for output to a stream.
+///
+/// `NO_COLOR` disables colors whenever it is set to a non-empty value.
+/// Otherwise, a `CLICOLOR_FORCE` other than `0` enables them and a `CLICOLOR`
+/// of `0` disables them. When none of those apply, colors are used only if the
+/// stream is a terminal.
+#[cfg(feature = "color")]
+fn use_color(is_terminal: bool) -> bool
+{
+	if env::var_os("NO_COLOR").map_or(false, |v| !v.is_empty()) {
+		return false;
+	}
+
+	if env::var_os("CLICOLOR_FORCE").map_or(false, |v| !v.is_empty() && v != "0") {
+		return true;
+	}
+
+	if env::var_os("CLICOLOR").map_or(false, |v| v == "0") {
+		return false;
+	}
+
+	is_terminal
+}
+
+/// A standard output stream of the process.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stream
+{
+	/// Standard output.
+	Stdout,
+
+	/// Standard error, where a `Terminator` returned from `main` is printed.
+	Stderr,
+}
+
+/// Prints an error in the same format as a `Terminator` to the given stream.
+///
+/// A `Terminator` returned from `main` is always printed to standard error.
+/// Tools that report errors as part of their output on standard output can
+/// handle the error in `main` themselves instead. When colors are detected
+/// automatically, they are based on the stream that is printed to.
+///
+/// ```no_run
+/// use easy_error::{err_msg, Error, Stream};
+///
+/// fn run() -> Result<(), Error> { Err(err_msg("Nothing to do")) }
+///
+/// fn main() {
+///     if let Err(err) = run() {
+///         easy_error::print_error(err, Stream::Stdout).expect("Could not print the error");
+///         std::process::exit(1);
+///     }
+/// }
+/// ```
+pub fn print_error<E: Into<Terminator>>(err: E, stream: Stream) -> io::Result<()>
+{
+	let term = err.into();
+	let term = Terminator { style: term.style.detect_color(stream), ..term };
+	match stream {
+		Stream::Stdout => {
+			let stdout = io::stdout();
+			term.render_to(stdout.lock())
+		},
+		Stream::Stderr => {
+			let stderr = io::stderr();
+			term.render_to(stderr.lock())
+		},
+	}
+}
+
+/// Replaces the panic hook with one that prints panics like a `Terminator`.
+///
+/// The panic message is printed along with its location, using the same
+/// colors and options as the `Terminator` output. This replaces the default
+/// hook, or any other that was set before, and is meant to be called at the
+/// start of `main`.
+///
+/// ```
+/// easy_error::install_panic_hook();
+///
+/// let result = std::panic::catch_unwind(|| panic!("Something went wrong"));
+/// assert!(result.is_err());
+/// ```
+pub fn install_panic_hook()
+{
+	panic::set_hook(Box::new(|info| {
+		let payload = info.payload();
+		let message = payload
+			.downcast_ref::<&str>()
+			.copied()
+			.or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+			.unwrap_or("Box<dyn Any>");
+
+		let style = Style::default().detect_color(Stream::Stderr);
+		let text = match info.location() {
+			Some(location) if !style.quiet() => {
+				format!("{} ({})", message, DisplayLocation(location))
+			},
+			_ => message.to_string(),
+		};
+
+		let mut output = String::new();
+		if paint(&mut output, format_args!("{}", text), "1;31", style.color()).is_ok() {
+			eprintln!("{}", output);
+		}
+	}));
+}
+
+/// Writes the error and its causes in the termination format.
+fn render(f: &mut dyn fmt::Write, err: &(dyn error::Error + 'static), style: Style) -> fmt::Result
+{
+	match style.line_ending {
+		LineEnding::Lf => write_report(f, err, style),
+		LineEnding::CrLf => write_report(&mut CrLfWriter { inner: f }, err, style),
+	}
+}
+
+/// Writes the error and its causes with `\n` line endings.
+fn write_report(
+	f: &mut dyn fmt::Write,
+	err: &(dyn error::Error + 'static),
+	style: Style,
+) -> fmt::Result
+{
+	let color = style.color();
+	let locations = style.locations();
+	let max_len = style.max_line_length;
+
+	let prefix = match style.layout {
+		Layout::Standard => "",
+		Layout::Compact => "error: ",
+	};
+	paint(f, format_args!("{}{}", prefix, Link { link: err, locations, max_len }), "1;31", color)?;
+	writeln!(f)?;
+	help(f, err, color)?;
+
+	let causes: Box<dyn Iterator<Item = _>> = if style.dedup {
+		Box::new(err.iter_chain_dedup().skip(1))
+	}
+	else {
+		Box::new(iter_causes(err))
+	};
+	write_causes(f, causes, style, color)?;
+
+	let scopes = iter_chain(err)
+		.filter_map(|e| e.downcast_ref::<Error>())
+		.map(Error::context_stack)
+		.filter(|stack| !stack.is_empty())
+		.last();
+	for scope in scopes.into_iter().flatten().rev() {
+		paint(f, format_args!("While:"), "1", color)?;
+		writeln!(f, " {}", scope)?;
+	}
+
+	let additional =
+		iter_chain(err).filter_map(|e| e.downcast_ref::<Error>()).flat_map(Error::also);
+	for extra in additional {
+		paint(f, format_args!("Additionally:"), "1", color)?;
+		writeln!(f, " {}", Link { link: extra, locations, max_len })?;
+		help(f, extra, color)?;
+		write_causes(f, iter_causes(extra), style, color)?;
+	}
+
+	#[cfg(feature = "sections")]
+	crate::section::write_sections(f, err, |f, title| {
+		paint(f, format_args!("{}", title), "1", color)
+	})?;
+
+	Ok(())
+}
+
+/// Writes a line for each of the causes, up to the maximum number of causes.
+///
+/// Consecutive causes that would print the same line are printed once, with
+/// the number of repeats.
+fn write_causes<'a, I>(f: &mut dyn fmt::Write, causes: I, style: Style, color: bool) -> fmt::Result
+where
+	I: Iterator<Item = &'a (dyn error::Error + 'static)>,
+{
+	let locations = style.locations();
+	let max_len = style.max_line_length;
+	let cause_label = style.cause_label();
+
+	let mut causes = causes.peekable();
+	let mut printed = 0;
+	while printed < style.max_causes.unwrap_or(usize::MAX) {
+		let cause = match causes.next() {
+			Some(cause) => cause,
+			None => break,
+		};
+
+		let line = Link { link: cause, locations, max_len }.to_string();
+		let mut repeats = 1;
+		while causes
+			.peek()
+			.map_or(false, |&next| Link { link: next, locations, max_len }.to_string() == line)
+		{
+			causes.next();
+			repeats += 1;
+		}
+
+		paint(f, format_args!("{}", cause_label), "1", color)?;
+		if repeats > 1 {
+			writeln!(f, " {} (x{})", line, repeats)?;
+		}
+		else {
+			writeln!(f, " {}", line)?;
+		}
+		help(f, cause, color)?;
+		printed += 1;
+	}
+
+	match causes.count() {
+		0 => Ok(()),
+		1 => writeln!(f, "... and 1 more cause"),
+		n => writeln!(f, "... and {} more causes", n),
+	}
+}
+
+/// A single link of the chain as it appears in the termination output.
+#[allow(clippy::struct_field_names)] // The link is what the whole line describes
+struct Link<'a>
+{
+	/// The link to display.
+	link: &'a (dyn error::Error + 'static),
+
+	/// Whether to include the location of the link, if it is known.
+	locations: Locations,
+
+	/// The maximum number of characters to display, if there is one.
+	max_len: Option<usize>,
+}
+
+impl Display for Link<'_>
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		let located = located(self.link);
+		match (&located, self.max_len) {
+			(Some((ctx, _)), None) => f.write_str(ctx)?,
+			(Some((ctx, _)), Some(max)) => f.write_str(&truncate(ctx.to_string(), max))?,
+			(None, None) => write!(f, "{}", self.link)?,
+			(None, Some(max)) => f.write_str(&truncate(self.link.to_string(), max))?,
+		}
+
+		if let Some((_, Some(location))) = located {
+			match self.locations {
+				Locations::Shown => write!(f, " ({})", location)?,
+				Locations::Placeholders => write!(f, " ({}:LINE:COL)", location.file())?,
+				Locations::Hidden => {},
+			}
+		}
+
+		let error = link_error(self.link);
+		let function = error.and_then(Error::function);
+		match (function, self.locations) {
+			(_, Locations::Hidden) | (None, _) => {},
+			(Some(function), _) => write!(f, " in {}", function)?,
+		}
+
+		#[cfg(feature = "sequence-id")]
+		if let Some(e) = error {
+			write!(f, " #{}", e.id())?;
+		}
+
+		#[cfg(feature = "thread-info")]
+		if let Some(name) = error.and_then(Error::thread) {
+			write!(f, " on thread {}", name)?;
 		}
 
 		Ok(())
 	}
 }
 
-impl<E: error::Error + 'static> From<E> for Terminator
+/// Shortens the message to the given number of characters, ending it with an
+/// ellipsis if anything was cut.
+fn truncate(line: String, max: usize) -> String
+{
+	if line.chars().nth(max).is_none() {
+		return line;
+	}
+	if max == 0 {
+		return String::new();
+	}
+
+	let end = line.char_indices().nth(max.saturating_sub(1)).map_or(0, |(i, _)| i);
+	format!("{}…", &line[..end])
+}
+
+/// Returns the message and location of the link, if it holds one of the types
+/// from this crate that record where they were created.
+fn located<'a>(link: &'a (dyn error::Error + 'static))
+-> Option<(Cow<'a, str>, Option<Origin<'a>>)>
+{
+	let from_error = split_location(link).map(|(message, e)| (message, e.origin()));
+	from_error.or_else(|| {
+		link.downcast_ref::<Context>()
+			.map(|c| (Cow::Borrowed(&*c.ctx), Some(Origin::Local(c.location))))
+	})
+}
+
+/// Renders the error into a new string.
+fn render_to_string(err: &(dyn error::Error + 'static), style: Style) -> String
+{
+	let mut output = String::new();
+	render(&mut output, err, style)
+		.expect("a Display implementation returned an error unexpectedly");
+	output
+}
+
+/// How the locations of errors are printed.
+#[derive(Clone, Copy)]
+enum Locations
+{
+	/// The full location is printed.
+	Shown,
+
+	/// The file is printed, with placeholders for the line and column.
+	Placeholders,
+
+	/// No location is printed.
+	Hidden,
+}
+
+/// Adapts an `io::Write` to the `fmt::Write` used for rendering.
+struct IoWriter<'a, W>
+{
+	/// The writer the output is written to.
+	inner: &'a mut W,
+
+	/// The error of the writer, if writing failed.
+	error: Option<io::Error>,
+}
+
+impl<W: io::Write> fmt::Write for IoWriter<'_, W>
+{
+	fn write_str(&mut self, s: &str) -> fmt::Result
+	{
+		self.inner.write_all(s.as_bytes()).map_err(|e| {
+			self.error = Some(e);
+			fmt::Error
+		})
+	}
+}
+
+/// Converts the line endings of the text written through it to `\r\n`.
+struct CrLfWriter<'a>
+{
+	/// The writer the output is written to.
+	inner: &'a mut dyn fmt::Write,
+}
+
+impl fmt::Write for CrLfWriter<'_>
+{
+	fn write_str(&mut self, s: &str) -> fmt::Result
+	{
+		for (i, line) in s.split('\n').enumerate() {
+			if i > 0 {
+				self.inner.write_str("\r\n")?;
+			}
+			self.inner.write_str(line)?;
+		}
+		Ok(())
+	}
+}
+
+/// Writes the documentation link of the link, if it has one.
+fn help(f: &mut dyn fmt::Write, link: &(dyn error::Error + 'static), color: bool) -> fmt::Result
+{
+	match link_error(link).and_then(Error::url) {
+		Some(url) => {
+			paint(f, format_args!("See:"), "1", color)?;
+			writeln!(f, " {}", url)
+		},
+		None => Ok(()),
+	}
+}
+
+/// Writes the text, surrounded by the ANSI escape code if colors are enabled.
+fn paint(f: &mut dyn fmt::Write, text: fmt::Arguments, code: &str, color: bool) -> fmt::Result
+{
+	if color { write!(f, "\x1b[{}m{}\x1b[0m", code, text) } else { write!(f, "{}", text) }
+}
+
+/// A message added on top of the error held by a `Terminator`.
+///
+/// The crate `Error` type requires its cause to be `Send`, which the contents
+/// of a `Terminator` are not, so this mirrors it without that requirement.
+#[derive(Debug)]
+struct Context
+{
+	ctx:      String,
+	location: &'static Location<'static>,
+	cause:    Box<dyn error::Error + 'static>,
+}
+
+impl Display for Context
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		write!(f, "{}", self.ctx)?;
+		#[cfg(not(feature = "no-location"))]
+		write!(f, " ({})", DisplayLocation(self.location))?;
+		Ok(())
+	}
+}
+
+impl error::Error for Context
+{
+	fn source(&self) -> Option<&(dyn error::Error + 'static)> { Some(&*self.cause) }
+}
+
+#[cfg(all(test, feature = "color"))]
+mod tests
 {
-	fn from(err: E) -> Terminator { Terminator { inner: Box::new(err) } }
+	use std::env;
+
+	use super::use_color;
+
+	// The environment is shared by the whole process, so every case lives in
+	// the same test to keep them from interfering with each other.
+	#[test]
+	fn use_color_follows_the_terminal_and_the_environment()
+	{
+		env::remove_var("NO_COLOR");
+		env::remove_var("CLICOLOR");
+		env::remove_var("CLICOLOR_FORCE");
+		assert!(use_color(true));
+		assert!(!use_color(false));
+
+		env::set_var("CLICOLOR", "0");
+		assert!(!use_color(true));
+		env::set_var("CLICOLOR", "1");
+		assert!(use_color(true));
+		assert!(!use_color(false));
+		env::remove_var("CLICOLOR");
+
+		env::set_var("CLICOLOR_FORCE", "1");
+		assert!(use_color(false));
+		env::set_var("CLICOLOR_FORCE", "0");
+		assert!(!use_color(false));
+		env::set_var("CLICOLOR_FORCE", "1");
+
+		env::set_var("NO_COLOR", "1");
+		assert!(!use_color(true));
+		env::set_var("NO_COLOR", "");
+		assert!(use_color(false));
+
+		env::remove_var("CLICOLOR_FORCE");
+		env::remove_var("NO_COLOR");
+	}
 }
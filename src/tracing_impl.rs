@@ -0,0 +1,39 @@
+//! Recording errors as `tracing` events.
+use crate::{Error, ErrorExt, iter_causes, message};
+
+impl Error
+{
+	/// Records the error as an event of the `ERROR` level.
+	///
+	/// Instead of one formatted message, the parts of the error are recorded
+	/// as separate fields, so that subscribers can index them:
+	///
+	/// * `error.message` - The message of the error, without its location.
+	/// * `error.location` - The location where the error was created. This is
+	///   left out with the `no-location` feature.
+	/// * `error.causes` - The messages of the causes, separated by `": "`.
+	/// * `error.depth` - The number of links in the chain.
+	///
+	/// ```
+	/// use easy_error::{err_msg, Error};
+	///
+	/// let err = Error::new("Upload failed", err_msg("Timed out"));
+	/// err.trace_event();
+	/// ```
+	pub fn trace_event(&self)
+	{
+		#[cfg(not(feature = "no-location"))]
+		let location = self.origin().map(|origin| origin.to_string());
+		#[cfg(feature = "no-location")]
+		let location: Option<String> = None;
+
+		let causes: Vec<_> = iter_causes(self).map(message).collect();
+
+		tracing::error!(
+			error.message = %self.ctx,
+			error.location = location.as_deref(),
+			error.causes = %causes.join(": "),
+			error.depth = self.depth(),
+		);
+	}
+}
@@ -0,0 +1,62 @@
+//! Compile-time checks of the auto traits and bounds of the public types.
+//!
+//! A change that breaks one of these shows up as a compilation failure of this
+//! file rather than a failing assertion.
+use std::{error, fmt, marker::PhantomData};
+
+use easy_error::{Error, MultiError, SendTerminator, Terminator};
+
+fn assert_send<T: Send>() {}
+fn assert_static<T: 'static>() {}
+fn assert_cause<T: error::Error + Send + 'static>() {}
+
+#[test]
+fn error_is_send()
+{
+	assert_send::<Error>();
+	assert_send::<easy_error::Result<()>>();
+	assert_send::<MultiError>();
+}
+
+#[test]
+fn errors_can_be_causes()
+{
+	assert_cause::<Error>();
+	assert_cause::<MultiError>();
+}
+
+/// An error that cannot be sent to other threads.
+#[derive(Debug)]
+struct NotSend(PhantomData<*const ()>);
+
+impl fmt::Display for NotSend
+{
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str("Not send") }
+}
+
+impl error::Error for NotSend {}
+
+#[test]
+fn terminator_accepts_errors_that_are_not_send()
+{
+	assert_static::<Terminator>();
+	let _ = Terminator::from(NotSend(PhantomData));
+}
+
+#[test]
+fn send_terminator_is_send()
+{
+	assert_send::<SendTerminator>();
+	assert_static::<SendTerminator>();
+	let _ = Terminator::from(SendTerminator::from(Error::from("abc".parse::<i32>().unwrap_err())));
+}
+
+#[test]
+#[cfg(not(any(feature = "sequence-id", feature = "thread-info")))]
+fn error_is_small()
+{
+	// The message, location, cause and a pointer to the rarely set parts. Older compilers need an
+	// extra word for the message.
+	assert!(std::mem::size_of::<Error>() <= 8 * std::mem::size_of::<usize>());
+	assert_eq!(std::mem::size_of::<easy_error::Result<()>>(), std::mem::size_of::<Error>());
+}
@@ -0,0 +1,41 @@
+#![cfg(feature = "color")]
+use std::env;
+
+use easy_error::{Terminator, err_msg};
+
+fn is_colored() -> bool { format!("{:?}", Terminator::from(err_msg("Bad value"))).contains('\x1b') }
+
+// The environment is shared by the whole process, so every case lives in the
+// same test to keep them from interfering with each other.
+#[test]
+fn environment_detection()
+{
+	env::remove_var("NO_COLOR");
+	env::remove_var("CLICOLOR");
+
+	env::set_var("CLICOLOR_FORCE", "1");
+	assert!(is_colored());
+
+	env::set_var("NO_COLOR", "1");
+	assert!(!is_colored());
+
+	env::set_var("NO_COLOR", "");
+	assert!(is_colored());
+
+	env::remove_var("CLICOLOR_FORCE");
+	env::set_var("CLICOLOR", "0");
+	assert!(!is_colored());
+
+	let forced = Terminator::from(err_msg("Bad value")).force_color(true);
+	assert!(format!("{:?}", forced).contains('\x1b'));
+
+	// Output that is not printed to a stream is only colored when forced.
+	env::set_var("CLICOLOR_FORCE", "1");
+	let term = Terminator::from(err_msg("Bad value"));
+	assert!(!term.rendered().contains('\x1b'));
+	assert!(!term.rendered_stable().contains('\x1b'));
+	let mut output = Vec::new();
+	term.render_to(&mut output).unwrap();
+	assert!(!output.contains(&0x1b));
+	assert!(forced.rendered().contains('\x1b'));
+}
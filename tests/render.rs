@@ -0,0 +1,71 @@
+//! Rendering of very deep chains, line endings and locations.
+use easy_error::{Error, ErrorExt, Terminator, err_msg};
+
+/// Builds a chain with the given number of links.
+fn deep_chain(depth: usize) -> Error
+{
+	let messages: Vec<_> = (0..depth).map(|i| format!("Layer {}", i)).collect();
+	let messages: Vec<_> = messages.iter().map(String::as_str).collect();
+	Error::chain_from(&messages)
+}
+
+#[test]
+fn max_causes_limits_deep_chain()
+{
+	let output = Terminator::from(deep_chain(10_000)).max_causes(3).quiet().rendered();
+
+	assert_eq!(output.lines().count(), 5);
+	assert!(output.ends_with("... and 9996 more causes\n"));
+}
+
+#[test]
+fn render_to_writes_deep_chain()
+{
+	let term = Terminator::from(deep_chain(10_000)).quiet();
+	let mut output = Vec::new();
+	term.render_to(&mut output).unwrap();
+
+	let output = String::from_utf8(output).unwrap();
+	assert_eq!(output.lines().count(), 10_000);
+	assert!(output.lines().last().unwrap().starts_with("Caused by: Layer 9999"));
+}
+
+#[test]
+fn line_endings()
+{
+	let lf = Terminator::from(deep_chain(3)).quiet().rendered();
+	assert_eq!(lf.matches('\n').count(), 3);
+	assert!(!lf.contains('\r'));
+
+	let crlf = Terminator::from(deep_chain(3)).quiet().crlf().rendered();
+	assert_eq!(crlf.matches("\r\n").count(), 3);
+	assert_eq!(crlf.matches('\n').count(), 3);
+	assert_eq!(crlf.lines().count(), lf.lines().count());
+}
+
+#[test]
+fn locations_of_combined_errors_follow_the_options()
+{
+	let combined = || Error::wrap_all(vec![err_msg("First"), err_msg("Second")]);
+	let quiet = Terminator::from(combined()).quiet().rendered();
+	assert!(quiet.lines().any(|l| l.starts_with("Caused by: Error 1 of 2: First")), "{}", quiet);
+	assert!(!quiet.contains(file!()));
+	assert!(!combined().chain_contains(file!()));
+
+	let chained = Error::with_cause_chain("Sync failed", vec![
+		Box::new(err_msg("Upload failed")) as Box<dyn std::error::Error + Send>,
+		Box::new(err_msg("Timed out")),
+	]);
+	let quiet = Terminator::from(chained).quiet().rendered();
+	assert!(quiet.lines().any(|l| l.starts_with("Caused by: Upload failed")), "{}", quiet);
+	assert!(!quiet.contains(file!()));
+
+	if !cfg!(feature = "no-location") {
+		let stable = Terminator::from(combined()).rendered_stable();
+		assert!(
+			stable.contains(&format!("Error 1 of 2: First ({}:LINE:COL)", file!())),
+			"{}",
+			stable
+		);
+	}
+}
@@ -0,0 +1,51 @@
+//! Round trips of errors through their serialized form.
+#![cfg(feature = "serde")]
+use std::io;
+
+use easy_error::{Error, ErrorExt, Terminator};
+use serde_json::Value;
+
+/// Returns the location of the error as it is written by `Serialize`.
+fn location_of(err: &Error) -> Value
+{
+	if cfg!(feature = "no-location") {
+		return Value::Null;
+	}
+
+	let location = err.location();
+	Value::from(format!("{}:{}:{}", location.file(), location.line(), location.column()))
+}
+
+#[test]
+fn round_trip()
+{
+	let timeout = io::Error::new(io::ErrorKind::TimedOut, "Timed out");
+	let upload = Error::new("Upload failed", timeout);
+	let upload_location = location_of(&upload);
+	let err = Error::new("Sync failed", upload);
+
+	let json = serde_json::to_value(&err).unwrap();
+	assert_eq!(json["message"], "Sync failed");
+	assert_eq!(json["location"], location_of(&err));
+	assert_eq!(json["causes"][0]["message"], "Upload failed");
+	assert_eq!(json["causes"][0]["location"], upload_location);
+	assert_eq!(json["causes"][1]["message"], "Timed out");
+	assert_eq!(json["causes"][1]["location"], Value::Null);
+	assert_eq!(json["causes"].as_array().unwrap().len(), 2);
+
+	let rebuilt: Error = serde_json::from_value(json.clone()).unwrap();
+	let messages: Vec<_> =
+		rebuilt.iter_chain().map(|e| e.downcast_ref::<Error>().unwrap().ctx()).collect();
+	assert_eq!(messages, ["Sync failed", "Upload failed", "Timed out"]);
+	assert_eq!(rebuilt.remote_location(), json["location"].as_str());
+	assert_eq!(rebuilt.to_string(), err.to_string());
+
+	// Serializing the rebuilt error again keeps the original locations.
+	assert_eq!(serde_json::to_value(&rebuilt).unwrap(), json);
+
+	let rendered = Terminator::from(rebuilt).rendered();
+	assert!(!rendered.contains("serde_impl.rs"));
+	if !cfg!(feature = "no-location") {
+		assert!(rendered.starts_with(&format!("Sync failed ({}", file!())));
+	}
+}
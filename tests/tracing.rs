@@ -0,0 +1,86 @@
+//! Recording errors as `tracing` events.
+#![cfg(feature = "tracing")]
+use std::{
+	collections::BTreeMap,
+	fmt::Debug,
+	sync::{Arc, Mutex},
+};
+
+use easy_error::{Error, err_msg};
+use tracing::{
+	Event,
+	Metadata,
+	Subscriber,
+	field::{Field, Visit},
+	span::{Attributes, Id, Record},
+};
+
+/// A subscriber that keeps the fields of every event as strings.
+#[derive(Clone, Default)]
+struct Capture
+{
+	events: Arc<Mutex<Vec<BTreeMap<String, String>>>>,
+}
+
+/// Collects the fields of a single event.
+struct Fields<'a>(&'a mut BTreeMap<String, String>);
+
+impl Visit for Fields<'_>
+{
+	fn record_str(&mut self, field: &Field, value: &str)
+	{
+		self.0.insert(field.name().to_string(), value.to_string());
+	}
+
+	fn record_debug(&mut self, field: &Field, value: &dyn Debug)
+	{
+		self.0.insert(field.name().to_string(), format!("{:?}", value));
+	}
+}
+
+impl Subscriber for Capture
+{
+	fn enabled(&self, _metadata: &Metadata<'_>) -> bool { true }
+
+	fn new_span(&self, _span: &Attributes<'_>) -> Id { Id::from_u64(1) }
+
+	fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+	fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+	fn event(&self, event: &Event<'_>)
+	{
+		let mut fields = BTreeMap::new();
+		event.record(&mut Fields(&mut fields));
+		self.events.lock().unwrap().push(fields);
+	}
+
+	fn enter(&self, _span: &Id) {}
+
+	fn exit(&self, _span: &Id) {}
+}
+
+#[test]
+fn trace_event_records_separate_fields()
+{
+	let capture = Capture::default();
+	let err = Error::new("Upload failed", Error::new("Connection lost", err_msg("Timed out")));
+	tracing::subscriber::with_default(capture.clone(), || err.trace_event());
+
+	let events = capture.events.lock().unwrap();
+	assert_eq!(events.len(), 1);
+
+	let fields = &events[0];
+	assert_eq!(fields["error.message"], "Upload failed");
+	assert_eq!(fields["error.causes"], "Connection lost: Timed out");
+	assert_eq!(fields["error.depth"], "3");
+
+	if cfg!(feature = "no-location") {
+		assert!(!fields.contains_key("error.location"));
+	}
+	else {
+		let location = err.location();
+		let expected = format!("{}:{}:{}", location.file(), location.line(), location.column());
+		assert_eq!(fields["error.location"], expected);
+	}
+}